@@ -0,0 +1,93 @@
+//! Compares [`nanval::decode`] against calling the individual predicates in order,
+//! and the SIMD [`nanval::simd::classify_batch`] against its scalar fallback.
+//! 
+//! Run with `cargo bench --bench classify`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use nanval::{cell, simd, uint, ValueKind};
+
+const ROUNDS: usize = 1_000;
+
+fn samples() -> Vec<u64> {
+    let mut samples = Vec::with_capacity(4096);
+    for i in 0..1024u64 {
+        samples.push((i as f64 * 0.5).to_bits());
+        samples.push(uint::wrap_unchecked(i));
+        samples.push(cell::from_tag_and_data(cell::CellTag::Tag4, i).unwrap());
+        samples.push(cell::CELL_MARKER_BITS | i);
+    }
+    samples
+}
+
+/// Classifies a value by calling the predicates one after another.
+fn classify_separately(bits: u64) -> u8 {
+    if nanval::is_float(bits) {
+        0
+    } else if uint::is_uint(bits) {
+        1
+    } else if cell::unwrap_tag(bits).is_some() {
+        2
+    } else {
+        3
+    }
+}
+
+/// Classifies a value via [`nanval::decode`].
+fn classify_decode(bits: u64) -> u8 {
+    match nanval::decode(bits) {
+        ValueKind::Float(_) => 0,
+        ValueKind::Uint(_) => 1,
+        ValueKind::Cell { .. } => 2,
+        ValueKind::Unknown(_) => 3,
+    }
+}
+
+fn bench(name: &str, samples: &[u64], classify: fn(u64) -> u8) -> Duration {
+    let start = Instant::now();
+    let mut sum = 0u64;
+    for _ in 0..ROUNDS {
+        for &bits in samples {
+            sum += classify(black_box(bits)) as u64;
+        }
+    }
+    let elapsed = start.elapsed();
+    black_box(sum);
+    println!("{name:>12}: {:?} per value", elapsed / (ROUNDS * samples.len()) as u32);
+    elapsed
+}
+
+type Batch = fn(&[u64], &mut Vec<f64>, &mut Vec<u64>, &mut Vec<u64>);
+
+fn bench_batch(name: &str, samples: &[u64], classify: Batch) -> Duration {
+    let (mut floats, mut uints, mut cells) = (Vec::new(), Vec::new(), Vec::new());
+    let start = Instant::now();
+    for _ in 0..ROUNDS {
+        floats.clear();
+        uints.clear();
+        cells.clear();
+        classify(black_box(samples), &mut floats, &mut uints, &mut cells);
+        black_box((&floats, &uints, &cells));
+    }
+    let elapsed = start.elapsed();
+    println!("{name:>12}: {:?} per batch of {}", elapsed / ROUNDS as u32, samples.len());
+    elapsed
+}
+
+fn main() {
+    let samples = samples();
+    bench("separately", &samples, classify_separately);
+    bench("decode", &samples, classify_decode);
+    
+    // Shuffled, so that the kinds of the values aren't trivially predictable.
+    let mut batch: Vec<u64> = samples.iter().copied().cycle().take(10_000).collect();
+    let mut seed = 0x9E3779B97F4A7C15u64;
+    for i in (1..batch.len()).rev() {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        batch.swap(i, (seed >> 33) as usize % (i + 1));
+    }
+    let scalar = bench_batch("scalar", &batch, simd::classify_batch_scalar);
+    let simd = bench_batch("simd", &batch, simd::classify_batch);
+    println!("{:>12}: {:.2}x", "speedup", scalar.as_secs_f64() / simd.as_secs_f64());
+}
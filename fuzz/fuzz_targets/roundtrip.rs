@@ -0,0 +1,9 @@
+//! Checks that every generated value survives a `decode` and `encode` roundtrip.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nanval::{IntoRawBits64, NanVal};
+
+fuzz_target!(|value: NanVal| {
+    nanval::invariants::assert_roundtrip(IntoRawBits64::as_raw_bits_64(&value));
+});
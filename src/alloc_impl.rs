@@ -0,0 +1,69 @@
+//! Conversion of heap-allocated [`Box`]es into cells and back, via [`alloc`]; for `no_std` environments with an allocator.
+
+use alloc::boxed::Box;
+use super::cell::{self, CellTag};
+
+/// Moves the given box into a cell with the given tag; see [`cell::from_tag_and_pointer`].
+/// 
+/// The cell then *owns* the allocation, which is leaked unless it is turned back into a box via [`cell_into_box`].
+/// 
+/// If the tag is reserved or the pointer doesn't fit into a cell, `None` is returned and the box is dropped.
+pub fn box_into_cell<T>(tag: CellTag, b: Box<T>) -> Option<u64> {
+    if cell::is_reserved_tag(tag) {return None}
+    let ptr = Box::into_raw(b);
+    match cell::from_tag_and_pointer(tag, ptr as *const ()) {
+        Some(value) => Some(value),
+        None => {
+            // SAFETY: The pointer was just created by `Box::into_raw`, and not handed out.
+            drop(unsafe { Box::from_raw(ptr) });
+            None
+        }
+    }
+}
+
+/// Turns the given cell back into the box it was created from via [`box_into_cell`], if it is a pointer-cell.
+/// 
+/// # Safety
+/// The same ownership rules as for [`Box::from_raw`] apply:
+/// the cell must have been created by [`box_into_cell`] with a box of the same type `T`,
+/// and it must not be turned back into a box more than once, or used after that.
+pub unsafe fn cell_into_box<T>(value: u64) -> Option<Box<T>> {
+    if !cell::is_pointer_cell(value) {return None}
+    let ptr = cell::unwrap_cell_rawptr(value)? as *mut T;
+    // SAFETY: Upheld by the caller; the pointer stems from `Box::into_raw` in `box_into_cell`.
+    Some(unsafe { Box::from_raw(ptr) })
+}
+
+#[cfg(test)]
+struct DropCounter<'a>(u64, &'a core::cell::Cell<usize>);
+
+#[cfg(test)]
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.1.set(self.1.get() + 1);
+    }
+}
+
+#[test]
+fn test_box_roundtrip() {
+    let drops = core::cell::Cell::new(0);
+    let value = box_into_cell(CellTag::Tag4, Box::new(DropCounter(42, &drops))).unwrap();
+    assert!(cell::is_cell_with_tag(value, CellTag::Tag4));
+    assert_eq!(drops.get(), 0);
+    
+    let b = unsafe { cell_into_box::<DropCounter>(value) }.unwrap();
+    assert_eq!(b.0, 42);
+    drop(b);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn test_box_invalid() {
+    let drops = core::cell::Cell::new(0);
+    assert_eq!(box_into_cell(CellTag::Tag1, Box::new(DropCounter(42, &drops))), None);
+    assert_eq!(drops.get(), 1);
+    
+    let data = cell::from_tag_and_data(CellTag::Tag2, 12345).unwrap();
+    assert!(unsafe { cell_into_box::<u64>(data) }.is_none());
+    assert!(unsafe { cell_into_box::<u64>(1.5f64.to_bits()) }.is_none());
+}
@@ -0,0 +1,67 @@
+//! Generation of NaN-tagged values for fuzzing, via [`arbitrary`].
+//! 
+//! [`NanVal`]s are generated as valid floats, uints and cells with roughly equal probability,
+//! plus the occasional arbitrary bit-pattern to probe the edge cases in between.
+
+use core::num::NonZeroU16;
+use arbitrary::{Arbitrary, Result, Unstructured};
+use super::{uint, wrap_float, NanVal};
+use super::rawval::RawNaNVal;
+
+#[cfg(feature = "cell")]
+use super::cell;
+
+impl<'a> Arbitrary<'a> for NanVal {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let bits = match u.int_in_range(0u8..=15)? {
+            0..=4 => wrap_float(f64::arbitrary(u)?),
+            5..=9 => uint::wrap_unchecked(u.int_in_range(0..=uint::MAX_VALUE)?),
+            #[cfg(feature = "cell")]
+            10..=14 => {
+                let tag = cell::CellTag::from_u8(u.int_in_range(1..=7)?).expect("the range only has valid tags");
+                cell::from_tag_and_data(tag, u.int_in_range(0..=cell::CELL_DATA_BITS)?).expect("the data always fits")
+            },
+            _ => u64::arbitrary(u)?
+        };
+        Ok(NanVal::from(bits))
+    }
+    
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(u8::size_hint(depth), u64::size_hint(depth))
+    }
+}
+
+impl<'a> Arbitrary<'a> for RawNaNVal<NonZeroU16, u32> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        match bool::arbitrary(u)? {
+            // A `NaN` with tag bits isn't a valid float, so it's replaced with the plain `NaN`.
+            true => Ok(Self::from_float(f64::arbitrary(u)?).unwrap_or_else(|| Self::from_float_unchecked(f64::NAN))),
+            false => Ok(Self::from_tag_and_data(NonZeroU16::arbitrary(u)?, u32::arbitrary(u)?))
+        }
+    }
+    
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(bool::size_hint(depth), u64::size_hint(depth))
+    }
+}
+
+#[test]
+fn test_arbitrary_nanval() {
+    let data: [u8; 256] = core::array::from_fn(|i| (i as u8).wrapping_mul(97));
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let value = NanVal::arbitrary(&mut u).unwrap();
+        crate::invariants::assert_roundtrip(crate::IntoRawBits64::as_raw_bits_64(&value));
+    }
+}
+
+#[test]
+fn test_arbitrary_rawval() {
+    let data: [u8; 256] = core::array::from_fn(|i| (i as u8).wrapping_mul(31));
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let value = RawNaNVal::<NonZeroU16, u32>::arbitrary(&mut u).unwrap();
+        assert!(value.has_f64() || value.get_tag_and_dat().is_some());
+        assert!(value.has_tag() || value.get_f64().is_some());
+    }
+}
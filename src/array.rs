@@ -0,0 +1,53 @@
+//! A fixed-size array of NaN-tagged values, [`ValueArray`], such as a register file.
+
+use super::{decode, NanVal, ValueKind};
+
+/// A fixed-size, stack-allocated, array of NaN-tagged values, initialized to the float `0.0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct ValueArray<const N: usize>([u64; N]);
+
+impl<const N: usize> ValueArray<N> {
+    /// Creates a new array, with all values set to the float `0.0`.
+    pub const fn new() -> Self {
+        Self([0; N])
+    }
+    
+    /// Decodes the value at the given index, if it is in bounds; see [`decode`].
+    pub fn get(&self, index: usize) -> Option<ValueKind> {
+        self.0.get(index).map(|&bits| decode(bits))
+    }
+    
+    /// Replaces the value at the given index, returning the previous value; or `None` if the index is out of bounds.
+    pub fn set(&mut self, index: usize, value: NanVal) -> Option<NanVal> {
+        let word = self.0.get_mut(index)?;
+        Some(NanVal(core::mem::replace(word, value.0)))
+    }
+    
+    /// Returns the raw bits of all values.
+    pub fn words(&self) -> &[u64; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Default for ValueArray<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[test]
+fn test_value_array() {
+    let mut array = ValueArray::<4>::new();
+    assert!(matches!(array.get(0), Some(ValueKind::Float(x)) if x == 0.0));
+    
+    assert_eq!(array.set(1, NanVal::from(1.5)), Some(NanVal::from(0.0)));
+    assert_eq!(array.set(3, NanVal::from(crate::uint::wrap_unchecked(42))), Some(NanVal::from(0.0)));
+    assert!(matches!(array.get(1), Some(ValueKind::Float(x)) if x == 1.5));
+    assert!(matches!(array.get(3), Some(ValueKind::Uint(42))));
+    assert_eq!(array.set(1, NanVal::from(2.5)), Some(NanVal::from(1.5)));
+    
+    assert!(array.get(4).is_none());
+    assert_eq!(array.set(4, NanVal::from(1.5)), None);
+    assert_eq!(array.words(), &[0, 2.5f64.to_bits(), 0, crate::uint::wrap_unchecked(42)]);
+}
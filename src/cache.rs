@@ -0,0 +1,96 @@
+//! A tiny least-recently-used cache, keyed on the raw bits of NaN-tagged values.
+
+use std::collections::VecDeque;
+use super::IntoRawBits64;
+
+/// A fixed-capacity cache that maps values to `V`, evicting the least recently used entry when full.
+///
+/// Keys are compared by their raw bits, so `0.0` and `-0.0` are distinct keys;
+/// use [`crate::canonicalize`] on the keys if that is not desired.
+///
+/// Lookups are linear, so this is only meant for small capacities.
+#[derive(Clone, Debug)]
+pub struct ValueCache<V> {
+    /// The entries, ordered from most to least recently used.
+    entries: VecDeque<(u64, V)>,
+    capacity: usize,
+}
+
+impl<V> ValueCache<V> {
+    /// Creates a new, empty, cache that holds at most `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity
+        }
+    }
+
+    /// Returns the number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns wether the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the value cached for the given key, marking it as the most recently used.
+    pub fn get(&mut self, key: impl IntoRawBits64) -> Option<&V> {
+        let key = key.as_raw_bits_64();
+        let index = self.entries.iter().position(|(k, _)| *k == key)?;
+        let entry = self.entries.remove(index)?;
+        self.entries.push_front(entry);
+        self.entries.front().map(|(_, v)| v)
+    }
+
+    /// Caches the given value for the given key, returning the previous value for the key, if any.
+    ///
+    /// If the cache is full, the least recently used entry is evicted.
+    pub fn insert(&mut self, key: impl IntoRawBits64, value: V) -> Option<V> {
+        if self.capacity == 0 {
+            return None
+        }
+        let key = key.as_raw_bits_64();
+        let old = self.entries.iter().position(|(k, _)| *k == key)
+            .and_then(|index| self.entries.remove(index))
+            .map(|(_, v)| v);
+        if self.entries.len() == self.capacity {
+            self.entries.pop_back();
+        }
+        self.entries.push_front((key, value));
+        old
+    }
+}
+
+#[test]
+fn test_cache_hit_miss() {
+    let mut cache = ValueCache::new(4);
+    assert_eq!(cache.get(1.5f64), None);
+    assert_eq!(cache.insert(1.5f64, "a"), None);
+    assert_eq!(cache.get(1.5f64), Some(&"a"));
+    assert_eq!(cache.get(crate::uint::wrap_unchecked(1)), None);
+    assert_eq!(cache.insert(1.5f64, "b"), Some("a"));
+    assert_eq!(cache.get(1.5f64), Some(&"b"));
+    assert_eq!(cache.len(), 1);
+}
+
+#[test]
+fn test_cache_eviction() {
+    let key = crate::uint::wrap_unchecked;
+    let mut cache = ValueCache::new(2);
+    cache.insert(key(1), 1);
+    cache.insert(key(2), 2);
+
+    // Using `1` makes `2` the least recently used entry.
+    assert_eq!(cache.get(key(1)), Some(&1));
+    cache.insert(key(3), 3);
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.get(key(2)), None);
+    assert_eq!(cache.get(key(1)), Some(&1));
+    assert_eq!(cache.get(key(3)), Some(&3));
+
+    let mut empty = ValueCache::new(0);
+    empty.insert(key(1), 1);
+    assert!(empty.is_empty());
+}
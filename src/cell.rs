@@ -11,12 +11,15 @@
 //! 
 //! **Note:** Is is *highly* recommended to always refer to these functions via the module; ie: `cell::XXX`.
 
-use super::{cons::*, IntoRawBits64};
+use super::{cons::*, IntoRawBits64, NanError};
 use core::num::NonZeroU64;
 
 /// Indicates that the value is a cell.
 pub const CELL_MARKER_BITS: u64 = SIGN_BIT | NAN_BITS;
 
+/// The [`CELL_MARKER_BITS`] as [`NonZeroU64`], for use in niche-optimized structures.
+pub const CELL_MARKER_BITS_NONZERO: NonZeroU64 = NonZeroU64::new(CELL_MARKER_BITS).unwrap();
+
 /// Masks the bits ([`CELL_MARKER_BITS`]) that indicate that the value is a cell.
 pub const CELL_MARKER_MASK: u64 = SIGN_BIT | NAN_BITS;
 
@@ -29,18 +32,18 @@ pub const CELL_TAG_BITS: u64 = 0x0007000000000000;
 /// Tag `0` is intentionally left undefined,
 /// to prevent the value ever accidentally
 /// becoming the original/sentinel `NaN`.
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[repr(u64)]
 pub enum CellTag {
     // Tag0 is intentionally undefined.
     
-    /// Tag `0b001`.
+    /// Tag `0b001`; reserved for singleton values, see [`SINGLETON_TAG_BITS`].
     Tag1 = 0x0001000000000000,
     
-    /// Tag `0b010`.
+    /// Tag `0b010`; reserved for symbols, see [`SYMBOL_TAG_BITS`].
     Tag2 = 0x0002000000000000,
     
-    /// Tag `0b011`.
+    /// Tag `0b011`; reserved for [`crate::sint`]s, see [`SINT_TAG_BITS`].
     Tag3 = 0x0003000000000000,
     
     /// Tag `0b100`.
@@ -56,6 +59,51 @@ pub enum CellTag {
     Tag7 = 0x0007000000000000,
 }
 
+impl CellTag {
+    /// Returns the tag of the given full NaN-tagged value, masking it with [`CELL_TAG_BITS`] first.
+    /// 
+    /// Unlike the `TryFrom<u64>` impl, which expects the *isolated* tag bits,
+    /// this does not require the marker- and data-bits to be cleared beforehand.
+    /// 
+    /// **Note:** This does *not* check if the value is a cell; use [`unwrap_tag`] for that.
+    #[inline(always)]
+    pub fn from_word(word: u64) -> Option<Self> {
+        Self::try_from(word & CELL_TAG_BITS).ok()
+    }
+    
+    /// Returns the tag with the given tag-index (`1..=7`), or `None` for any other index.
+    #[inline(always)]
+    pub fn from_u8(tag: u8) -> Option<Self> {
+        match tag {
+            1..=7 => Self::try_from((tag as u64) << 48).ok(),
+            _ => None
+        }
+    }
+    
+    /// Returns the tag-index (`1..=7`) of the tag.
+    #[inline(always)]
+    pub fn to_u8(self) -> u8 {
+        ((self as u64) >> 48) as u8
+    }
+}
+
+/// Prints the name of the tag, `Tag1` through `Tag7`.
+impl core::fmt::Display for CellTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Converts the tag-index (`1..=7`); see [`CellTag::from_u8`].
+impl TryFrom<u8> for CellTag {
+    type Error = (); // error left as unit type
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_u8(value).ok_or(())
+    }
+}
+
+/// Converts the *isolated* tag bits, as in [`CELL_TAG_BITS`]; prefer [`CellTag::from_u8`] or [`CellTag::from_word`].
+#[doc(hidden)]
 impl TryFrom<u64> for CellTag {
     type Error = (); // error left as unit type
     fn try_from(value: u64) -> Result<Self, Self::Error> {
@@ -98,8 +146,20 @@ pub const CELL_TAG_7: u64 = CellTag::Tag7 as u64;
 /// Masks out the data of a [`CELL_MARKER_BITS`]-marked value.
 pub const CELL_DATA_BITS: u64 = !(CELL_MARKER_BITS | CELL_TAG_BITS);
 
+// Compile-time checks that the marker, tag and data bits partition the value,
+// and that cells and uints only differ in the sign-bit of their marker.
+const _: () = {
+    assert!(CELL_MARKER_BITS & CELL_TAG_BITS == 0);
+    assert!(CELL_MARKER_BITS & CELL_DATA_BITS == 0);
+    assert!(CELL_TAG_BITS & CELL_DATA_BITS == 0);
+    assert!(CELL_MARKER_BITS | CELL_TAG_BITS | CELL_DATA_BITS == u64::MAX);
+    assert!(CELL_MARKER_BITS == crate::uint::UINT_MARKER_BITS | SIGN_BIT);
+    assert!(MAX_CELL_PTR_ADDR == CELL_DATA_BITS);
+};
+
 /// Ensure that the bit-patterns do not overlap.
 #[test]
+#[allow(clippy::assertions_on_constants)]
 pub fn test_cell_bits() {
     assert!(CELL_MARKER_BITS != CELL_TAG_BITS);
     assert!(CELL_MARKER_BITS != CELL_DATA_BITS);
@@ -107,8 +167,50 @@ pub fn test_cell_bits() {
     assert!(CELL_MARKER_BITS & CELL_TAG_BITS & CELL_DATA_BITS == 0);
     assert!(CELL_MARKER_BITS | CELL_TAG_BITS | CELL_DATA_BITS == u64::MAX);
     assert!(CELL_MARKER_BITS ^ CELL_TAG_BITS ^ CELL_DATA_BITS == u64::MAX);
+    assert!(CELL_MARKER_BITS_NONZERO.get() == CELL_MARKER_BITS);
+}
+
+#[test]
+fn test_cell_tag_u8() {
+    for index in 1..=7u8 {
+        let tag = CellTag::from_u8(index).unwrap();
+        assert_eq!(tag.to_u8(), index);
+        assert_eq!(tag as u64, (index as u64) << 48);
+        assert_eq!(CellTag::try_from(index), Ok(tag));
+    }
+    assert_eq!(CellTag::from_u8(0), None);
+    assert_eq!(CellTag::from_u8(8), None);
+    assert_eq!(CellTag::try_from(255u8), Err(()));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_cell_tag_traits() {
+    assert_eq!(CellTag::Tag4, CellTag::Tag4);
+    assert_ne!(CellTag::Tag4, CellTag::Tag5);
+    assert_eq!(format!("{}", CellTag::Tag1), "Tag1");
+    assert_eq!(format!("{}", CellTag::Tag7), "Tag7");
+    
+    let mut counts = std::collections::HashMap::new();
+    for tag in [CellTag::Tag2, CellTag::Tag3, CellTag::Tag2] {
+        *counts.entry(tag).or_insert(0) += 1;
+    }
+    assert_eq!(counts[&CellTag::Tag2], 2);
+    assert_eq!(extract_tag_and_data(symbol(1).unwrap()).map(|(tag, _)| tag), Some(CellTag::Tag2));
 }
 
+#[test]
+#[cfg(feature = "std")]
+fn test_cell_tag_ord() {
+    let mut handlers = std::collections::BTreeMap::new();
+    for index in [5, 1, 7, 3, 2, 6, 4] {
+        handlers.insert(CellTag::from_u8(index).unwrap(), index);
+    }
+    assert!(handlers.values().copied().eq(1..=7));
+    assert!(CellTag::Tag1 < CellTag::Tag7);
+}
+
+/// Ensure that a pointer survives being stored in a cell.
 #[test]
 #[cfg(feature = "std")]
 pub fn test_ptr_roundtrip() {
@@ -122,7 +224,13 @@ pub fn test_ptr_roundtrip() {
 /// Returns wether the given value is a cell.
 #[inline(always)]
 pub fn is_cell(value: impl IntoRawBits64) -> bool {
-    (value.as_raw_bits_64() & CELL_MARKER_BITS) == CELL_MARKER_BITS
+    is_cell_u64(value.as_raw_bits_64())
+}
+
+/// Returns wether the given bits are a cell; the `const` version of [`is_cell`].
+#[inline(always)]
+pub const fn is_cell_u64(value: u64) -> bool {
+    (value & CELL_MARKER_BITS) == CELL_MARKER_BITS
 }
 
 /// Returns wether the given value is *not* a cell.
@@ -131,6 +239,105 @@ pub fn is_not_cell(value: impl IntoRawBits64) -> bool {
     (value.as_raw_bits_64() & CELL_MARKER_BITS) != CELL_MARKER_BITS
 }
 
+/// Returns wether the given value is a cell with the given tag, in a single mask-and-compare.
+#[inline(always)]
+pub fn is_cell_with_tag(value: impl IntoRawBits64, tag: CellTag) -> bool {
+    is_cell_with_tag_u64(value.as_raw_bits_64(), tag as u64)
+}
+
+/// Returns wether the given bits are a cell with the given isolated tag bits; the `const` version of [`is_cell_with_tag`].
+#[inline(always)]
+pub(crate) const fn is_cell_with_tag_u64(value: u64, tag: u64) -> bool {
+    (value & (CELL_MARKER_MASK | CELL_TAG_BITS)) == CELL_MARKER_BITS | tag
+}
+
+/// Returns wether the given bits are a cell with [`CellTag::Tag1`].
+#[inline(always)]
+pub const fn is_cell_tag1(value: u64) -> bool {
+    is_cell_with_tag_u64(value, CELL_TAG_1)
+}
+
+/// Returns wether the given bits are a cell with [`CellTag::Tag2`].
+#[inline(always)]
+pub const fn is_cell_tag2(value: u64) -> bool {
+    is_cell_with_tag_u64(value, CELL_TAG_2)
+}
+
+/// Returns wether the given bits are a cell with [`CellTag::Tag3`].
+#[inline(always)]
+pub const fn is_cell_tag3(value: u64) -> bool {
+    is_cell_with_tag_u64(value, CELL_TAG_3)
+}
+
+/// Returns wether the given bits are a cell with [`CellTag::Tag4`].
+#[inline(always)]
+pub const fn is_cell_tag4(value: u64) -> bool {
+    is_cell_with_tag_u64(value, CELL_TAG_4)
+}
+
+/// Returns wether the given bits are a cell with [`CellTag::Tag5`].
+#[inline(always)]
+pub const fn is_cell_tag5(value: u64) -> bool {
+    is_cell_with_tag_u64(value, CELL_TAG_5)
+}
+
+/// Returns wether the given bits are a cell with [`CellTag::Tag6`].
+#[inline(always)]
+pub const fn is_cell_tag6(value: u64) -> bool {
+    is_cell_with_tag_u64(value, CELL_TAG_6)
+}
+
+/// Returns wether the given bits are a cell with [`CellTag::Tag7`].
+#[inline(always)]
+pub const fn is_cell_tag7(value: u64) -> bool {
+    is_cell_with_tag_u64(value, CELL_TAG_7)
+}
+
+#[test]
+fn test_is_cell_with_tag() {
+    let checks = [is_cell_tag1, is_cell_tag2, is_cell_tag3, is_cell_tag4, is_cell_tag5, is_cell_tag6, is_cell_tag7];
+    for index in 1..=7u8 {
+        let tag = CellTag::from_u8(index).unwrap();
+        let cell = from_tag_and_data(tag, CELL_DATA_BITS).unwrap();
+        for other in 1..=7u8 {
+            assert_eq!(is_cell_with_tag(cell, CellTag::from_u8(other).unwrap()), index == other);
+            assert_eq!(checks[other as usize - 1](cell), index == other);
+        }
+        // A uint with the same bits below the sign-bit is not a cell.
+        assert!(!is_cell_with_tag(cell & !SIGN_BIT, tag));
+    }
+    assert!(!is_cell_tag4(1.5f64.to_bits()));
+    const { assert!(is_cell_tag2(CELL_MARKER_BITS | CELL_TAG_2 | 42)) };
+}
+
+/// Returns wether the given value is a pointer-cell, with a tag in `4..=7`; see [`POINTER_CELL_TAG_BIT`].
+#[inline(always)]
+pub fn is_pointer_cell(value: impl IntoRawBits64) -> bool {
+    let bits = value.as_raw_bits_64();
+    is_cell(bits) && bits & POINTER_CELL_TAG_BIT != 0
+}
+
+/// Returns wether the given value is a data-cell, with a tag in `1..=3`; see [`POINTER_CELL_TAG_BIT`].
+/// 
+/// A cell with the undefined tag `0` is neither a data- nor a pointer-cell.
+#[inline(always)]
+pub fn is_data_cell(value: impl IntoRawBits64) -> bool {
+    let bits = value.as_raw_bits_64();
+    is_cell(bits) && bits & CELL_TAG_BITS != 0 && bits & POINTER_CELL_TAG_BIT == 0
+}
+
+#[test]
+fn test_pointer_and_data_cells() {
+    for index in 1..=7u8 {
+        let cell = from_tag_and_data(CellTag::from_u8(index).unwrap(), 42).unwrap();
+        assert_eq!(is_pointer_cell(cell), index >= 4);
+        assert_eq!(is_data_cell(cell), index <= 3);
+    }
+    assert!(!is_pointer_cell(CELL_MARKER_BITS | 42) && !is_data_cell(CELL_MARKER_BITS | 42));
+    assert!(!is_pointer_cell(crate::uint::wrap_unchecked(POINTER_CELL_TAG_BIT)));
+    assert!(!is_data_cell(1.5f64));
+}
+
 /// Returns the tag bits of the given value.
 #[inline(always)]
 pub fn unwrap_tag_bits_unchecked(value: impl IntoRawBits64) -> u64 {
@@ -170,6 +377,21 @@ pub fn unwrap_cell(value: impl IntoRawBits64) -> Option<u64> {
     }
 }
 
+/// Unwraps the raw 48-bit data of the given value, if it is a cell; the counterpart of [`from_tag_and_data`].
+/// 
+/// This is identical to [`unwrap_cell`], named for clarity when the cell holds data instead of a pointer.
+/// 
+/// ```
+/// use nanval::cell::{self, CellTag};
+/// let value = cell::from_tag_and_data(CellTag::Tag4, 12345).unwrap();
+/// assert_eq!(cell::unwrap_cell_data(value), Some(12345));
+/// assert_eq!(cell::unwrap_cell_data(1.5f64), None);
+/// ```
+#[inline(always)]
+pub fn unwrap_cell_data(value: impl IntoRawBits64) -> Option<u64> {
+    unwrap_cell(value)
+}
+
 /// Unwraps the cell-data of the given value as [`NonZeroU64`], if it is a cell.
 #[inline(always)]
 pub fn unwrap_cell_nonzero(value: impl IntoRawBits64) -> Option<NonZeroU64> {
@@ -191,10 +413,127 @@ pub fn unwrap_cell_rawptr(value: impl IntoRawBits64) -> Option<*const ()> {
     }
 }
 
+/// Returns the 48 data bits of *any* cell as `*const ()`, regardless of its tag; for GC scanning and the like.
+/// 
+/// Unlike [`unwrap_as_ref`] and [`unwrap_as_mut`], this makes no assumption about what the tag means:
+/// the data of a data-cell, such as a [`NULL`] or a [`from_tag_resource`] value,
+/// is returned just the same, despite not being an address at all.
+/// 
+/// **Note:** The caller must know the semantics of the cells tag before treating the result as a pointer.
+/// The pointer is created from an integer, so it only carries provenance if the address was exposed
+/// when the cell was created (as with `ptr as u64`), and it must not be dereferenced otherwise.
+#[inline(always)]
+pub fn any_cell_ptr(value: impl IntoRawBits64) -> Option<*const ()> {
+    match is_cell(value) {
+        true => Some(unwrap_cell_unchecked(value) as *const ()),
+        false => None
+    }
+}
+
+#[test]
+fn test_any_cell_ptr() {
+    let ptr = 0x1000 as *const ();
+    assert_eq!(any_cell_ptr(from_tag_and_pointer(CellTag::Tag4, ptr).unwrap()), Some(ptr));
+    assert_eq!(any_cell_ptr(from_tag_and_data(CellTag::Tag2, 12345).unwrap()), Some(12345 as *const ()));
+    assert_eq!(any_cell_ptr(UNDEFINED).map(|ptr| ptr as u64), Some(2));
+    assert_eq!(any_cell_ptr(1.5f64), None);
+    assert_eq!(any_cell_ptr(crate::uint::wrap_unchecked(42)), None);
+}
+
+/// Returns a well-distributed hash of the 48-bit payload of the given value, if it is a cell; for cell-keyed maps.
+/// 
+/// The payload of a pointer-cell mostly differs in its low bits, so it is multiplied by the 64-bit golden ratio,
+/// which spreads the low bits upwards, and then rotated, to also spread the high bits downwards.
+/// 
+/// **Note:** Only the payload is hashed; cells that differ only in their tag have the same hash.
+#[inline(always)]
+pub fn payload_hash(value: impl IntoRawBits64) -> Option<u64> {
+    let data = unwrap_cell(value)?;
+    Some(data.wrapping_mul(0x9E3779B97F4A7C15).rotate_left(32))
+}
+
+#[test]
+fn test_payload_hash() {
+    let hash = |addr: u64| payload_hash(from_tag_and_pointer(CellTag::Tag4, addr as *const ()).unwrap()).unwrap();
+    
+    // Neighbouring, aligned, pointers differ in both the high and the low bits of their hashes.
+    assert_ne!(hash(0x1000) >> 48, hash(0x1008) >> 48);
+    assert_ne!(hash(0x1000) & 0xFFFF, hash(0x1008) & 0xFFFF);
+    
+    let mut high = [false; 256];
+    for index in 0..256 {
+        high[(hash(0x10000 + index * 16) >> 56) as usize] = true;
+    }
+    let distinct = high.iter().filter(|&&seen| seen).count();
+    assert!(distinct > 128, "only {distinct} distinct high bytes");
+    assert_eq!(payload_hash(1.5f64), None);
+}
+
+/// Combines the given tag and typed pointer into a NaN-tagged value; see [`from_tag_and_pointer`].
+/// 
+/// Storing a pointer is safe; only dereferencing it again, via [`unwrap_as_ref`] or [`unwrap_as_mut`], is not.
+#[inline(always)]
+pub fn from_typed_ptr<T>(tag: CellTag, ptr: *const T) -> Option<u64> {
+    from_tag_and_pointer(tag, ptr as *const ())
+}
+
+/// Unwraps the pointer of the given cell as a shared reference to a `T`.
+/// 
+/// # Safety
+/// The value must have been created by [`from_typed_ptr`] with the same `T`,
+/// and the pointer must be non-null, aligned for `T` and valid for reads during `'a`,
+/// with no mutable reference to the `T` existing during `'a`.
+#[inline(always)]
+pub unsafe fn unwrap_as_ref<'a, T>(value: impl IntoRawBits64) -> Option<&'a T> {
+    let ptr = unwrap_cell_rawptr(value)? as *const T;
+    // SAFETY: Upheld by the caller.
+    unsafe { ptr.as_ref() }
+}
+
+/// Unwraps the pointer of the given cell as a mutable reference to a `T`.
+/// 
+/// # Safety
+/// The value must have been created by [`from_typed_ptr`] with the same `T`,
+/// and the pointer must be non-null, aligned for `T` and valid for reads and writes during `'a`,
+/// with no other reference to the `T` existing during `'a`.
+#[inline(always)]
+pub unsafe fn unwrap_as_mut<'a, T>(value: impl IntoRawBits64) -> Option<&'a mut T> {
+    let ptr = unwrap_cell_rawptr(value)? as *mut T;
+    // SAFETY: Upheld by the caller.
+    unsafe { ptr.as_mut() }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_typed_ptr() {
+    let ptr = Box::into_raw(Box::new(4711u32));
+    let cell = from_typed_ptr(CellTag::Tag4, ptr).unwrap();
+    assert_eq!(unsafe { unwrap_as_ref::<u32>(cell) }, Some(&4711));
+    *unsafe { unwrap_as_mut::<u32>(cell) }.unwrap() += 1;
+    assert_eq!(unsafe { unwrap_as_ref::<u32>(cell) }, Some(&4712));
+    assert_eq!(unsafe { unwrap_as_ref::<u32>(1.5f64) }, None);
+    drop(unsafe { Box::from_raw(ptr) });
+}
+
+/// Returns wether the address of the given pointer is small enough to be stored in a cell; see [`MAX_CELL_PTR_ADDR`].
+#[inline(always)]
+pub fn fits_in_cell_ptr(ptr: *const ()) -> bool {
+    (ptr as u64) <= MAX_CELL_PTR_ADDR
+}
+
+/// Returns wether the given tag is reserved by this crate; see [`RESERVED_CELL_TAG_BITS`].
+#[inline(always)]
+pub fn is_reserved_tag(tag: CellTag) -> bool {
+    RESERVED_CELL_TAG_BITS.contains(&(tag as u64))
+}
+
 /// Combines the given tag and pointer into a NaN-tagged value.
 /// 
+/// The `tag` is the logical tag; use [`CellTag::from_u8`] to get it from a tag-index (`1..=7`).
+/// 
 /// If either the `tag` or the `ptr` don't fit in the limits
 /// imposed by [`CELL_TAG_BITS`] and [`CELL_DATA_BITS`],
+/// or the `tag` is reserved (see [`is_reserved_tag`]),
 /// this function will return `None`.
 /// 
 /// # Safety
@@ -205,14 +544,785 @@ pub fn unwrap_cell_rawptr(value: impl IntoRawBits64) -> Option<*const ()> {
 /// 
 /// However, performing any kind of logic- or arithmetic-operations
 /// on the returned value, will result in undefined behaviour.
+/// 
+/// # Panics
+/// In debug builds, this function panics if the bits of `ptr` lie in NaN-space,
+/// which usually means that an already NaN-tagged value was passed instead of a pointer.
 pub fn from_tag_and_pointer(tag: CellTag, ptr: *const ()) -> Option<u64> {
+    debug_assert!(
+        (ptr as u64) & NAN_BITS != NAN_BITS,
+        "the pointer {ptr:?} looks like a NaN-tagged value; was an already boxed value passed?"
+    );
+    if is_reserved_tag(tag) {return None}
     from_tag_and_data(tag, ptr as u64)
 }
 
-pub fn from_tag_and_data(tag: CellTag, data: u64) -> Option<u64> {
+/// Combines the given tag and data into a NaN-tagged value.
+/// 
+/// Unlike [`from_tag_and_pointer`], this stores any raw 48-bit integer and accepts every tag.
+/// 
+/// The `tag` is the logical tag; use [`CellTag::from_u8`] to get it from a tag-index (`1..=7`).
+/// 
+/// If either the `tag` or the `data` don't fit in the limits
+/// imposed by [`CELL_TAG_BITS`] and [`CELL_DATA_BITS`],
+/// this function will return `None`.
+#[inline(always)]
+pub const fn from_tag_and_data(tag: CellTag, data: u64) -> Option<u64> {
     let vtag = (tag as u64) & CELL_TAG_BITS;
     let vdata = data & CELL_DATA_BITS;
     if vtag != tag as u64 {return None}
     if vdata != data {return None}
     Some(CELL_MARKER_BITS | vtag | vdata)
 }
+
+/// An owned byte-buffer, whose data-pointer is stored in a cell.
+/// 
+/// The buffer is reconstructed and freed when the [`CellBox`] is dropped.
+#[cfg(feature = "std")]
+pub struct CellBox {
+    cell: u64,
+    len: usize,
+}
+
+#[cfg(feature = "std")]
+impl CellBox {
+    /// Takes ownership of the given buffer, storing its data-pointer in a cell with the given tag.
+    /// 
+    /// If the pointer doesn't fit into a cell, the buffer is handed back as-is.
+    pub fn new(tag: CellTag, buffer: Box<[u8]>) -> Result<Self, Box<[u8]>> {
+        let len = buffer.len();
+        let ptr = buffer.as_ptr() as *const ();
+        match from_tag_and_pointer(tag, ptr) {
+            Some(cell) => {
+                // The allocation is now owned by the cell.
+                core::mem::forget(buffer);
+                Ok(Self {cell, len})
+            },
+            None => Err(buffer)
+        }
+    }
+    
+    /// Returns the NaN-tagged value holding the data-pointer.
+    #[inline(always)]
+    pub fn cell(&self) -> u64 {
+        self.cell
+    }
+    
+    /// Returns the tag the buffer was boxed with.
+    #[inline(always)]
+    pub fn tag(&self) -> CellTag {
+        // The tag was valid when the cell was created.
+        CellTag::try_from(unwrap_tag_bits_unchecked(self.cell)).unwrap()
+    }
+    
+    #[inline(always)]
+    fn as_mut_ptr(&self) -> *mut u8 {
+        unwrap_cell_unchecked(self.cell) as *mut u8
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::ops::Deref for CellBox {
+    type Target = [u8];
+    
+    fn deref(&self) -> &[u8] {
+        // The pointer and length stem from a live `Box<[u8]>` owned by `self`.
+        unsafe {core::slice::from_raw_parts(self.as_mut_ptr(), self.len)}
+    }
+}
+
+#[cfg(feature = "std")]
+impl Drop for CellBox {
+    fn drop(&mut self) {
+        // Reconstruct the original box, so that it gets freed exactly once.
+        let slice = core::ptr::slice_from_raw_parts_mut(self.as_mut_ptr(), self.len);
+        drop(unsafe {Box::from_raw(slice)});
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_cell_box() {
+    let buffer: Box<[u8]> = vec![1, 2, 3, 4].into_boxed_slice();
+    let cell_box = CellBox::new(CellTag::Tag5, buffer).unwrap();
+    assert!(is_cell(cell_box.cell()));
+    assert!(matches!(cell_box.tag(), CellTag::Tag5));
+    assert_eq!(&*cell_box, &[1, 2, 3, 4]);
+    
+    let empty = CellBox::new(CellTag::Tag5, Box::new([])).unwrap();
+    assert!(empty.is_empty());
+}
+
+/// Masks the flag-bit of a cell-value created via [`from_tag_flag_u47`]; the topmost data bit.
+pub const CELL_FLAG_BIT: u64 = 0x0000800000000000;
+
+/// Masks the value-bits of a cell-value created via [`from_tag_flag_u47`]; the lower 47 data bits.
+pub const CELL_U47_BITS: u64 = CELL_DATA_BITS & !CELL_FLAG_BIT;
+
+/// Combines the given tag, flag and 47-bit value into a NaN-tagged value.
+/// 
+/// The flag is stored in the topmost data bit ([`CELL_FLAG_BIT`]),
+/// the value in the remaining 47 bits ([`CELL_U47_BITS`]).
+/// 
+/// If the `value` doesn't fit into 47 bits, this function will return `None`.
+pub fn from_tag_flag_u47(tag: CellTag, flag: bool, value: u64) -> Option<u64> {
+    if value & CELL_U47_BITS != value {return None}
+    let flag = if flag {CELL_FLAG_BIT} else {0};
+    from_tag_and_data(tag, flag | value)
+}
+
+/// Unwraps the flag and 47-bit value of the given value, if it is a cell.
+#[inline(always)]
+pub fn unwrap_cell_flag_u47(value: impl IntoRawBits64) -> Option<(bool, u64)> {
+    let data = unwrap_cell(value)?;
+    Some((data & CELL_FLAG_BIT != 0, data & CELL_U47_BITS))
+}
+
+#[test]
+fn test_flag_u47_roundtrip() {
+    for flag in [false, true] {
+        for value in [0, 1, 12345, CELL_U47_BITS] {
+            let cell = from_tag_flag_u47(CellTag::Tag2, flag, value).unwrap();
+            assert!(matches!(unwrap_tag(cell), Some(CellTag::Tag2)));
+            assert_eq!(unwrap_cell_flag_u47(cell), Some((flag, value)));
+        }
+    }
+    assert!(from_tag_flag_u47(CellTag::Tag2, false, CELL_U47_BITS + 1).is_none());
+    assert!(unwrap_cell_flag_u47(1.5f64).is_none());
+}
+
+#[test]
+fn test_cell_tag_from_word() {
+    let cell = from_tag_and_data(CellTag::Tag6, 0x1234).unwrap();
+    assert!(matches!(CellTag::from_word(cell), Some(CellTag::Tag6)));
+    assert!(matches!(CellTag::try_from(cell & CELL_TAG_BITS), Ok(CellTag::Tag6)));
+    assert!(CellTag::try_from(cell).is_err());
+    assert!(CellTag::from_word(CELL_MARKER_BITS | 0x1234).is_none());
+}
+
+#[test]
+fn test_reserved_tag_pointer() {
+    let ptr = &0u64 as *const u64 as *const ();
+    for tag in [CellTag::Tag1, CellTag::Tag2, CellTag::Tag3] {
+        assert!(is_reserved_tag(tag));
+        assert!(from_tag_and_pointer(tag, ptr).is_none());
+        assert!(from_tag_and_data(tag, 0).is_some());
+    }
+    for tag in [CellTag::Tag4, CellTag::Tag5, CellTag::Tag6, CellTag::Tag7] {
+        assert!(!is_reserved_tag(tag));
+        assert_eq!(from_tag_and_pointer(tag, ptr).and_then(unwrap_cell_rawptr), Some(ptr));
+    }
+}
+
+/// Combines the given tag and the 32-bit byte-offset of `ptr` from `base` into a NaN-tagged value.
+/// 
+/// If `ptr` lies before `base`, or more than `u32::MAX` bytes after it,
+/// this function will return `None`.
+pub fn from_tag_offset(tag: CellTag, base: *const (), ptr: *const ()) -> Option<u64> {
+    let offset = (ptr as u64).checked_sub(base as u64)?;
+    let offset = u32::try_from(offset).ok()?;
+    from_tag_and_data(tag, offset as u64)
+}
+
+/// Unwraps the 32-bit byte-offset of the given value as a pointer relative to `base`, if it is a cell.
+/// 
+/// If the cell-data doesn't fit into 32 bits, this function will return `None`.
+/// 
+/// # Safety
+/// This function cannot check if the returned pointer is valid.
+pub fn unwrap_cell_offset(value: impl IntoRawBits64, base: *const ()) -> Option<*const ()> {
+    let offset = u32::try_from(unwrap_cell(value)?).ok()?;
+    Some((base as *const u8).wrapping_add(offset as usize) as *const ())
+}
+
+#[test]
+fn test_offset_roundtrip() {
+    let heap = [0u64; 16];
+    let base = heap.as_ptr() as *const ();
+    let ptr = &heap[9] as *const u64 as *const ();
+    
+    let cell = from_tag_offset(CellTag::Tag4, base, ptr).unwrap();
+    assert_eq!(unwrap_cell(cell), Some(9 * 8));
+    assert_eq!(unwrap_cell_offset(cell, base), Some(ptr));
+    
+    assert!(from_tag_offset(CellTag::Tag4, ptr, base).is_none());
+    #[cfg(target_pointer_width = "64")]
+    {
+        let far = (base as usize + (1 << 32)) as *const ();
+        assert!(from_tag_offset(CellTag::Tag4, base, far).is_none());
+    }
+    
+    let wide = from_tag_and_data(CellTag::Tag4, 1 << 32).unwrap();
+    assert!(unwrap_cell_offset(wide, base).is_none());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "looks like a NaN-tagged value")]
+fn test_nested_pointer_guard() {
+    from_tag_and_pointer(CellTag::Tag4, NAN_BITS as *const ());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "looks like a NaN-tagged value")]
+fn test_nested_cell_guard() {
+    let cell = from_tag_and_data(CellTag::Tag4, 0x1234).unwrap();
+    from_tag_and_pointer(CellTag::Tag4, cell as *const ());
+}
+
+/// Creates a symbol from the given 48-bit interned id; a cell with the reserved [`SYMBOL_TAG_BITS`].
+/// 
+/// If the `id` doesn't fit into [`CELL_DATA_BITS`], this function will return `None`.
+#[inline(always)]
+pub fn symbol(id: u64) -> Option<u64> {
+    from_tag_and_data(CellTag::Tag2, id)
+}
+
+/// Returns wether the given value is a symbol.
+#[inline(always)]
+pub fn is_symbol(value: impl IntoRawBits64) -> bool {
+    value.as_raw_bits_64() & (CELL_MARKER_BITS | CELL_TAG_BITS) == CELL_MARKER_BITS | SYMBOL_TAG_BITS
+}
+
+/// Unwraps the interned id of the given value, if it is a symbol.
+#[inline(always)]
+pub fn unwrap_symbol_id(value: impl IntoRawBits64) -> Option<u64> {
+    match is_symbol(value) {
+        true => Some(unwrap_cell_unchecked(value)),
+        false => None
+    }
+}
+
+#[test]
+fn test_symbol() {
+    for id in [0, 1, 4711, CELL_DATA_BITS] {
+        let sym = symbol(id).unwrap();
+        assert!(is_cell(sym));
+        assert!(is_symbol(sym));
+        assert_eq!(unwrap_symbol_id(sym), Some(id));
+    }
+    assert!(symbol(CELL_DATA_BITS + 1).is_none());
+    
+    let other = from_tag_and_data(CellTag::Tag3, 4711).unwrap();
+    assert!(!is_symbol(other));
+    assert_eq!(unwrap_symbol_id(other), None);
+    assert!(!is_symbol(crate::uint::UINT_MARKER_BITS | SYMBOL_TAG_BITS));
+    assert!(!is_symbol(1.5f64));
+}
+
+/// Extracts both the tag and the data of the given value, if it is a cell with a defined tag.
+/// 
+/// This is the single-check equivalent of calling [`unwrap_tag`] and [`unwrap_cell`].
+#[inline(always)]
+pub fn extract_tag_and_data(value: impl IntoRawBits64) -> Option<(CellTag, u64)> {
+    let bits = value.as_raw_bits_64();
+    if !is_cell(bits) {return None}
+    let tag = CellTag::try_from(bits & CELL_TAG_BITS).ok()?;
+    Some((tag, bits & CELL_DATA_BITS))
+}
+
+/// Extracts both the tag and the data of the given value, without any checks.
+/// 
+/// # Safety
+/// The value must be a cell with a defined (non-zero) tag, as checked by [`unwrap_tag`];
+/// otherwise an invalid [`CellTag`] is created, which is undefined behaviour.
+#[inline(always)]
+pub unsafe fn extract_tag_and_data_unchecked(value: impl IntoRawBits64) -> (CellTag, u64) {
+    let bits = value.as_raw_bits_64();
+    debug_assert!(unwrap_tag(bits).is_some(), "the value {bits:#018x} is not a cell with a defined tag");
+    // SAFETY: The caller guarantees that the tag bits are one of the `CellTag` discriminants.
+    let tag = unsafe { core::mem::transmute::<u64, CellTag>(bits & CELL_TAG_BITS) };
+    (tag, bits & CELL_DATA_BITS)
+}
+
+#[test]
+fn test_extract_tag_and_data() {
+    let tags = [CellTag::Tag1, CellTag::Tag2, CellTag::Tag3, CellTag::Tag4, CellTag::Tag5, CellTag::Tag6, CellTag::Tag7];
+    for tag in tags {
+        for data in [0, 1, 0xBEEF, CELL_DATA_BITS] {
+            let cell = from_tag_and_data(tag, data).unwrap();
+            let (t, d) = extract_tag_and_data(cell).unwrap();
+            assert_eq!((t as u64, d), (tag as u64, data));
+            let (t, d) = unsafe { extract_tag_and_data_unchecked(cell) };
+            assert_eq!((t as u64, d), (tag as u64, data));
+        }
+    }
+    assert!(extract_tag_and_data(1.5f64).is_none());
+    assert!(extract_tag_and_data(crate::uint::wrap_unchecked(42)).is_none());
+    assert!(extract_tag_and_data(CELL_MARKER_BITS | 42).is_none());
+}
+
+/// Returns a copy of the given cell with its tag replaced, keeping the data bits; or `None` if it isn't a cell.
+#[inline(always)]
+pub fn replace_tag(value: impl IntoRawBits64, new_tag: CellTag) -> Option<u64> {
+    let bits = value.as_raw_bits_64();
+    match is_cell(bits) {
+        true => Some((bits & !CELL_TAG_BITS) | new_tag as u64),
+        false => None
+    }
+}
+
+/// Returns a copy of the given cell with its data replaced, keeping the tag bits.
+/// 
+/// Returns `None` if the value isn't a cell, or if the `new_data` doesn't fit into the [`CELL_DATA_BITS`].
+#[inline(always)]
+pub fn replace_data(value: impl IntoRawBits64, new_data: u64) -> Option<u64> {
+    let bits = value.as_raw_bits_64();
+    match is_cell(bits) && new_data & CELL_DATA_BITS == new_data {
+        true => Some((bits & !CELL_DATA_BITS) | new_data),
+        false => None
+    }
+}
+
+#[test]
+fn test_replace_tag_and_data() {
+    let cell = from_tag_and_data(CellTag::Tag4, 0xBEEF).unwrap();
+    
+    let retagged = replace_tag(cell, CellTag::Tag6).unwrap();
+    assert!(matches!(unwrap_tag(retagged), Some(CellTag::Tag6)));
+    assert_eq!(retagged & !CELL_TAG_BITS, cell & !CELL_TAG_BITS);
+    
+    let redata = replace_data(cell, CELL_DATA_BITS).unwrap();
+    assert_eq!(unwrap_cell(redata), Some(CELL_DATA_BITS));
+    assert_eq!(redata & !CELL_DATA_BITS, cell & !CELL_DATA_BITS);
+    
+    assert_eq!(replace_data(cell, CELL_DATA_BITS + 1), None);
+    assert_eq!(replace_tag(1.5f64, CellTag::Tag4), None);
+    assert_eq!(replace_data(crate::uint::wrap_unchecked(1), 2), None);
+}
+
+/// Unwraps the tag-index (`1..=7`) and the sign-extended 48-bit cell-data of the given value, if it is a cell.
+/// 
+/// If the tag is `0b000` (undefined), this function will return `None`.
+#[inline(always)]
+pub fn unwrap_tag_and_signed(value: impl IntoRawBits64) -> Option<(u8, i64)> {
+    let tag = unwrap_tag_bits(value)?;
+    if tag == 0 {return None}
+    // Shift the 48 data bits to the top, so that the arithmetic shift back down sign-extends them.
+    let data = ((value.as_raw_bits_64() << 16) as i64) >> 16;
+    Some(((tag >> 48) as u8, data))
+}
+
+#[test]
+fn test_unwrap_tag_and_signed() {
+    let cell = from_tag_and_data(CellTag::Tag3, (-4711i64 as u64) & CELL_DATA_BITS).unwrap();
+    assert_eq!(unwrap_tag_and_signed(cell), Some((3, -4711)));
+    
+    let cell = from_tag_and_data(CellTag::Tag7, 4711).unwrap();
+    assert_eq!(unwrap_tag_and_signed(cell), Some((7, 4711)));
+    
+    let cell = from_tag_and_data(CellTag::Tag1, CELL_DATA_BITS).unwrap();
+    assert_eq!(unwrap_tag_and_signed(cell), Some((1, -1)));
+    
+    assert_eq!(unwrap_tag_and_signed(CELL_MARKER_BITS | 42), None);
+    assert_eq!(unwrap_tag_and_signed(1.5f64), None);
+}
+
+#[test]
+fn test_const_from_tag_and_data() {
+    const CELL: Option<u64> = from_tag_and_data(CellTag::Tag4, 42);
+    assert_eq!(CELL.and_then(unwrap_cell), Some(42));
+    assert!(matches!(CELL.and_then(unwrap_tag), Some(CellTag::Tag4)));
+}
+
+#[test]
+fn test_fits_in_cell_ptr() {
+    assert!(fits_in_cell_ptr(&0u64 as *const u64 as *const ()));
+    #[cfg(target_pointer_width = "64")]
+    {
+        let limit = MAX_CELL_PTR_ADDR as usize as *const ();
+        assert!(fits_in_cell_ptr(limit));
+        assert!(from_tag_and_pointer(CellTag::Tag4, limit).is_some());
+        
+        let beyond = (MAX_CELL_PTR_ADDR + 1) as usize as *const ();
+        assert!(!fits_in_cell_ptr(beyond));
+        assert!(from_tag_and_pointer(CellTag::Tag4, beyond).is_none());
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_cell_tag_debug() {
+    assert_eq!(format!("{:?}", CellTag::Tag3), "Tag3");
+}
+
+/// Combines the given tag and the `(a, b, id)` triple into a NaN-tagged value.
+/// 
+/// The data bits are laid out as follows, from most to least significant:
+/// ```text
+/// aaaa aaaa bbbb bbbb iiii iiii iiii iiii iiii iiii iiii iiii
+/// ```
+#[inline(always)]
+pub fn from_tag_u8_u8_u32(tag: CellTag, a: u8, b: u8, id: u32) -> Option<u64> {
+    from_tag_and_data(tag, (a as u64) << 40 | (b as u64) << 32 | id as u64)
+}
+
+/// Unwraps the `(a, b, id)` triple of the given value, if it is a cell; see [`from_tag_u8_u8_u32`].
+#[inline(always)]
+pub fn unwrap_cell_u8_u8_u32(value: impl IntoRawBits64) -> Option<(u8, u8, u32)> {
+    let data = unwrap_cell(value)?;
+    Some(((data >> 40) as u8, (data >> 32) as u8, data as u32))
+}
+
+#[test]
+fn test_u8_u8_u32_roundtrip() {
+    for triple in [(0, 0, 0), (u8::MAX, 0, 0), (0, u8::MAX, 0), (0, 0, u32::MAX), (u8::MAX, u8::MAX, u32::MAX), (1, 2, 3)] {
+        let cell = from_tag_u8_u8_u32(CellTag::Tag5, triple.0, triple.1, triple.2).unwrap();
+        assert!(matches!(unwrap_tag(cell), Some(CellTag::Tag5)));
+        assert_eq!(unwrap_cell_u8_u8_u32(cell), Some(triple));
+    }
+    assert_eq!(unwrap_cell_u8_u8_u32(1.5f64), None);
+}
+
+/// The largest value of either half of a pair; see [`from_tag_and_pair`].
+pub const MAX_PAIR_HALF: u32 = (1 << 24) - 1;
+
+/// Combines the given tag and the 24-bit `hi` and `lo` halves into a NaN-tagged value.
+/// 
+/// The data bits are laid out as follows, from most to least significant:
+/// ```text
+/// hhhh hhhh hhhh hhhh hhhh hhhh llll llll llll llll llll llll
+/// ```
+/// So `hi` is stored in the data bits 47–24, and `lo` in the data bits 23–0.
+/// 
+/// Returns `None` if either half is larger than [`MAX_PAIR_HALF`].
+#[inline(always)]
+pub fn from_tag_and_pair(tag: CellTag, hi: u32, lo: u32) -> Option<u64> {
+    if hi > MAX_PAIR_HALF || lo > MAX_PAIR_HALF {return None}
+    from_tag_and_data(tag, (hi as u64) << 24 | lo as u64)
+}
+
+/// Unwraps the 24-bit `(hi, lo)` halves of the given value, if it is a cell; see [`from_tag_and_pair`].
+#[inline(always)]
+pub fn split_hi_lo(value: impl IntoRawBits64) -> Option<(u32, u32)> {
+    let data = unwrap_cell(value)?;
+    Some(((data >> 24) as u32, data as u32 & MAX_PAIR_HALF))
+}
+
+#[test]
+fn test_pair_roundtrip() {
+    for (hi, lo) in [(0, 0), (MAX_PAIR_HALF, 0), (0, MAX_PAIR_HALF), (MAX_PAIR_HALF, MAX_PAIR_HALF), (12, 34)] {
+        let cell = from_tag_and_pair(CellTag::Tag5, hi, lo).unwrap();
+        assert_eq!(split_hi_lo(cell), Some((hi, lo)));
+    }
+    assert_eq!(unwrap_cell(from_tag_and_pair(CellTag::Tag5, 1, 0).unwrap()), Some(1 << 24));
+    assert_eq!(from_tag_and_pair(CellTag::Tag5, MAX_PAIR_HALF + 1, 0), None);
+    assert_eq!(from_tag_and_pair(CellTag::Tag5, 0, MAX_PAIR_HALF + 1), None);
+    assert_eq!(split_hi_lo(1.5f64), None);
+}
+
+/// The largest id that can be stored in a resource handle; see [`from_tag_resource`].
+pub const MAX_RESOURCE_ID: u64 = (1 << 40) - 1;
+
+/// Combines the given tag, 8-bit resource `kind` and 40-bit `id` into a NaN-tagged value, such as an FFI handle.
+/// 
+/// The data bits are laid out as follows, from most to least significant:
+/// ```text
+/// kkkk kkkk iiii iiii iiii iiii iiii iiii iiii iiii iiii iiii
+/// ```
+/// 
+/// Returns `None` if the `id` is larger than [`MAX_RESOURCE_ID`].
+#[inline(always)]
+pub fn from_tag_resource(tag: CellTag, kind: u8, id: u64) -> Option<u64> {
+    if id > MAX_RESOURCE_ID {return None}
+    from_tag_and_data(tag, (kind as u64) << 40 | id)
+}
+
+/// Unwraps the `(kind, id)` of the given value, if it is a cell; see [`from_tag_resource`].
+#[inline(always)]
+pub fn unwrap_cell_resource(value: impl IntoRawBits64) -> Option<(u8, u64)> {
+    let data = unwrap_cell(value)?;
+    Some(((data >> 40) as u8, data & MAX_RESOURCE_ID))
+}
+
+#[test]
+fn test_resource_roundtrip() {
+    for (kind, id) in [(0, 0), (255, 0), (0, MAX_RESOURCE_ID), (255, MAX_RESOURCE_ID), (3, 42)] {
+        let cell = from_tag_resource(CellTag::Tag6, kind, id).unwrap();
+        assert!(matches!(unwrap_tag(cell), Some(CellTag::Tag6)));
+        assert_eq!(unwrap_cell_resource(cell), Some((kind, id)));
+    }
+    assert_eq!(from_tag_resource(CellTag::Tag6, 0, 1 << 40), None);
+    assert_eq!(unwrap_cell_resource(1.5f64), None);
+}
+
+/// Combines the given tag, 32-bit slot `index` and 16-bit `generation` into a NaN-tagged value, as for a slot-map.
+/// 
+/// The data bits are laid out as follows, from most to least significant:
+/// ```text
+/// gggg gggg gggg gggg iiii iiii iiii iiii iiii iiii iiii iiii
+/// ```
+/// Both always fit, so this never fails; use [`validate_handle`] to get the index back.
+#[inline(always)]
+pub fn from_tag_handle(tag: CellTag, index: u32, generation: u16) -> u64 {
+    CELL_MARKER_BITS | tag as u64 | (generation as u64) << 32 | index as u64
+}
+
+/// Unwraps the index of the given handle, if it is a cell with the expected generation; see [`from_tag_handle`].
+/// 
+/// Returns [`NanError::WrongKind`] if the value isn't a cell at all,
+/// and [`NanError::StaleHandle`] if its generation doesn't match; ie: the slot was freed and reused since.
+#[inline(always)]
+pub fn validate_handle(value: u64, expected_gen: u16) -> Result<u32, NanError> {
+    let data = unwrap_cell(value).ok_or(NanError::WrongKind)?;
+    match (data >> 32) as u16 == expected_gen {
+        true => Ok(data as u32),
+        false => Err(NanError::StaleHandle)
+    }
+}
+
+#[test]
+fn test_handle_generation() {
+    let handle = from_tag_handle(CellTag::Tag5, 42, 7);
+    assert!(is_cell_with_tag(handle, CellTag::Tag5));
+    assert_eq!(validate_handle(handle, 7), Ok(42));
+    assert_eq!(validate_handle(handle, 8), Err(NanError::StaleHandle));
+    assert_eq!(validate_handle(from_tag_handle(CellTag::Tag5, u32::MAX, u16::MAX), u16::MAX), Ok(u32::MAX));
+    assert_eq!(validate_handle(1.5f64.to_bits(), 7), Err(NanError::WrongKind));
+}
+
+/// Combines the given tag and `&'static str` into a NaN-tagged value, relative to the given `base` pointer.
+/// 
+/// The length of the string is stored in the upper 8 data bits, and its offset from the `base` in the lower 40 data bits,
+/// same as the kind and id of [`from_tag_resource`].
+/// 
+/// Returns `None` if the string starts before the `base`, is too far from it, or is longer than 255 bytes.
+#[inline(always)]
+pub fn from_tag_static_str(tag: CellTag, s: &'static str, base: *const u8) -> Option<u64> {
+    let offset = (s.as_ptr() as u64).checked_sub(base as u64)?;
+    let len = u8::try_from(s.len()).ok()?;
+    from_tag_resource(tag, len, offset)
+}
+
+/// Unwraps the `&'static str` of the given value, relative to the given `base` pointer; see [`from_tag_static_str`].
+/// 
+/// # Safety
+/// The value must have been created by [`from_tag_static_str`] with the same `base`.
+#[inline(always)]
+pub unsafe fn unwrap_static_str(value: impl IntoRawBits64, base: *const u8) -> Option<&'static str> {
+    let (len, offset) = unwrap_cell_resource(value)?;
+    // SAFETY: Upheld by the caller; the bytes were a `&'static str` when the value was created.
+    unsafe {
+        let bytes = core::slice::from_raw_parts(base.add(offset as usize), len as usize);
+        Some(core::str::from_utf8_unchecked(bytes))
+    }
+}
+
+#[test]
+fn test_static_str_roundtrip() {
+    static TABLE: &str = "nullfalsetrueundefined";
+    let base = TABLE.as_ptr();
+    for s in [&TABLE[0..4], &TABLE[4..9], &TABLE[9..13], &TABLE[13..], &TABLE[0..0]] {
+        let cell = from_tag_static_str(CellTag::Tag4, s, base).unwrap();
+        assert_eq!(unsafe { unwrap_static_str(cell, base) }, Some(s));
+    }
+    assert_eq!(from_tag_static_str(CellTag::Tag4, &TABLE[1..], TABLE[2..].as_ptr()), None);
+    assert_eq!(unsafe { unwrap_static_str(1.5f64, base) }, None);
+}
+
+/// Ensure that the full 48-bit payload survives for every tag, and that one bit beyond is rejected.
+#[test]
+fn test_full_payload_roundtrip() {
+    let tags = [CellTag::Tag1, CellTag::Tag2, CellTag::Tag3, CellTag::Tag4, CellTag::Tag5, CellTag::Tag6, CellTag::Tag7];
+    for tag in tags {
+        let cell = from_tag_and_data(tag, CELL_DATA_BITS).unwrap();
+        assert_eq!(unwrap_cell(cell), Some(CELL_DATA_BITS));
+        assert_eq!(unwrap_tag_bits(cell), Some(tag as u64));
+        assert!(from_tag_and_data(tag, CELL_DATA_BITS + 1).is_none());
+    }
+}
+
+/// The singleton representing an absent optional value; see [`wrap_some`].
+pub const NONE: u64 = CELL_MARKER_BITS | SINGLETON_TAG_BITS;
+
+/// The singleton representing `null`; distinct from [`UNDEFINED`] and [`NONE`].
+pub const NULL: u64 = CELL_MARKER_BITS | SINGLETON_TAG_BITS | 1;
+
+/// The singleton representing `undefined`; distinct from [`NULL`] and [`NONE`].
+pub const UNDEFINED: u64 = CELL_MARKER_BITS | SINGLETON_TAG_BITS | 2;
+
+/// The singleton representing an error, such as the result of a division by zero.
+pub const ERROR: u64 = CELL_MARKER_BITS | SINGLETON_TAG_BITS | 3;
+
+/// Returns wether the given value is the [`NULL`] singleton.
+#[inline(always)]
+pub fn is_null(value: impl IntoRawBits64) -> bool {
+    value.as_raw_bits_64() == NULL
+}
+
+/// Returns wether the given value is the [`UNDEFINED`] singleton.
+#[inline(always)]
+pub fn is_undefined(value: impl IntoRawBits64) -> bool {
+    value.as_raw_bits_64() == UNDEFINED
+}
+
+#[test]
+fn test_null_and_undefined() {
+    assert_ne!(NULL, UNDEFINED);
+    assert_ne!(NULL, NONE);
+    assert!(is_null(NULL) && !is_undefined(NULL));
+    assert!(is_undefined(UNDEFINED) && !is_null(UNDEFINED));
+    assert!(!is_null(NONE) && !is_undefined(NONE));
+    assert!(is_data_cell(NULL) && is_data_cell(UNDEFINED));
+    assert!([NONE, NULL, UNDEFINED].iter().all(|&v| v != ERROR));
+    assert!(is_data_cell(ERROR) && !is_null(ERROR));
+}
+
+/// Wraps the given value as a present optional value.
+/// 
+/// A NaN-tagged value can't be nested inside another, so a present value is stored *as itself*,
+/// with the [`NONE`] singleton taking the place of the absent value.
+/// Hence only uints and cells with a defined tag can be wrapped, and `None` is returned for anything else,
+/// including floats and [`NONE`] itself.
+#[inline(always)]
+pub fn wrap_some(inner: impl IntoRawBits64) -> Option<u64> {
+    let inner = inner.as_raw_bits_64();
+    let valid = crate::uint::is_uint(inner) || unwrap_tag(inner).is_some();
+    (valid && inner != NONE).then_some(inner)
+}
+
+/// Unwraps the given optional value; see [`wrap_some`].
+/// 
+/// Returns `Some(None)` for [`NONE`], `Some(Some(inner))` for a present value,
+/// and `None` if the value isn't an optional value at all.
+#[inline(always)]
+pub fn unwrap_some(value: impl IntoRawBits64) -> Option<Option<u64>> {
+    match value.as_raw_bits_64() {
+        NONE => Some(None),
+        value => wrap_some(value).map(Some)
+    }
+}
+
+#[test]
+fn test_optional_value() {
+    assert_eq!(unwrap_some(NONE), Some(None));
+    assert!(is_cell(NONE));
+    
+    let uint = crate::uint::wrap_unchecked(42);
+    let some = wrap_some(uint).unwrap();
+    assert_eq!(unwrap_some(some), Some(Some(uint)));
+    
+    let sym = symbol(7).unwrap();
+    assert_eq!(wrap_some(sym).and_then(unwrap_some), Some(Some(sym)));
+    
+    assert_eq!(wrap_some(NONE), None);
+    assert_eq!(wrap_some(1.5f64), None);
+    assert_eq!(unwrap_some(1.5f64), None);
+}
+
+/// Creates a cons-cell: a cell with the given tag, pointing to a two-word `car`/`cdr` pair.
+/// 
+/// The pointer must point to the following heap layout, as is the case for a `[u64; 2]`:
+/// ```text
+/// ptr + 0: car (a NaN-tagged value)
+/// ptr + 8: cdr (a NaN-tagged value)
+/// ```
+/// 
+/// The tag is chosen by the caller, as cons-cells are just one of many possible pointer types;
+/// the same rules as for [`from_tag_and_pointer`] apply, so reserved tags return `None`.
+#[inline(always)]
+pub fn cons_cell(car_ptr: *const [u64; 2], tag: CellTag) -> Option<u64> {
+    from_tag_and_pointer(tag, car_ptr as *const ())
+}
+
+/// Returns wether the given value is a cons-cell with the given tag; see [`cons_cell`].
+#[inline(always)]
+pub fn is_cons(value: impl IntoRawBits64, tag: CellTag) -> bool {
+    is_cell_with_tag(value, tag)
+}
+
+/// Unwraps the pointer to the `car`/`cdr` pair of the given value, if it is a cons-cell with the given tag; see [`cons_cell`].
+/// 
+/// # Safety
+/// This function cannot check if the returned pointer is valid.
+#[inline(always)]
+pub fn unwrap_cons_ptr(value: impl IntoRawBits64, tag: CellTag) -> Option<*const [u64; 2]> {
+    match is_cons(value, tag) {
+        true => Some(unwrap_cell_unchecked(value) as *const [u64; 2]),
+        false => None
+    }
+}
+
+#[test]
+fn test_cons_cell() {
+    let pair: [u64; 2] = [crate::uint::wrap_unchecked(1), 2.5f64.to_bits()];
+    let cons = cons_cell(&pair, CellTag::Tag4).unwrap();
+    assert!(is_cons(cons, CellTag::Tag4));
+    assert!(!is_cons(cons, CellTag::Tag5));
+    assert!(!is_cons(1.5f64, CellTag::Tag4));
+    
+    let ptr = unwrap_cons_ptr(cons, CellTag::Tag4).unwrap();
+    assert_eq!(ptr, &pair as *const [u64; 2]);
+    let [car, cdr] = unsafe { *ptr };
+    assert_eq!(crate::uint::unwrap_uint(car), Some(1));
+    assert_eq!(crate::unwrap_float(cdr), Some(2.5));
+    
+    assert_eq!(unwrap_cons_ptr(cons, CellTag::Tag5), None);
+    assert_eq!(cons_cell(&pair, CellTag::Tag1), None);
+}
+
+/// A non-null pointer to a `T`, stored in a cell; see [`tag_nonnull`].
+/// 
+/// Unlike a raw cell from [`from_tag_and_pointer`], the pointer can never be null and its type is carried along.
+#[repr(transparent)]
+pub struct TaggedNonNull<T> {
+    cell: u64,
+    _type: core::marker::PhantomData<core::ptr::NonNull<T>>,
+}
+
+/// Stores the given non-null pointer in a cell with the given tag.
+/// 
+/// Returns `None` in the same cases as [`from_tag_and_pointer`].
+#[inline(always)]
+pub fn tag_nonnull<T>(tag: CellTag, ptr: core::ptr::NonNull<T>) -> Option<TaggedNonNull<T>> {
+    let cell = from_tag_and_pointer(tag, ptr.as_ptr() as *const ())?;
+    Some(TaggedNonNull { cell, _type: core::marker::PhantomData })
+}
+
+impl<T> TaggedNonNull<T> {
+    /// Returns the stored pointer.
+    #[inline(always)]
+    pub fn as_non_null(&self) -> core::ptr::NonNull<T> {
+        // SAFETY: The cell was created from a `NonNull`, and the pointer survives being stored in a cell.
+        unsafe { core::ptr::NonNull::new_unchecked(unwrap_cell_unchecked(self.cell) as *mut T) }
+    }
+    
+    /// Returns the tag the pointer was stored with.
+    #[inline(always)]
+    pub fn tag(&self) -> CellTag {
+        // The tag was valid when the cell was created.
+        CellTag::try_from(unwrap_tag_bits_unchecked(self.cell)).unwrap()
+    }
+    
+    /// Returns the NaN-tagged value holding the pointer.
+    #[inline(always)]
+    pub fn cell(&self) -> u64 {
+        self.cell
+    }
+}
+
+impl<T> Clone for TaggedNonNull<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TaggedNonNull<T> {}
+
+impl<T> IntoRawBits64 for TaggedNonNull<T> {
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        self.cell
+    }
+}
+
+#[test]
+fn test_tagged_nonnull() {
+    let x = 4711u32;
+    let tagged = tag_nonnull(CellTag::Tag6, core::ptr::NonNull::from(&x)).unwrap();
+    assert_eq!(tagged.tag(), CellTag::Tag6);
+    assert!(is_cell_with_tag(tagged, CellTag::Tag6));
+    assert_eq!(unsafe { *tagged.as_non_null().as_ref() }, 4711);
+    assert!(tag_nonnull(CellTag::Tag1, core::ptr::NonNull::from(&x)).is_none());
+}
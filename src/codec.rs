@@ -0,0 +1,106 @@
+//! Boxing of a user-defined value type, via its conversions from and into [`ValueKind`].
+
+use core::marker::PhantomData;
+use super::{decode, encode, NanError, ValueKind};
+
+/// Encodes and decodes a user-defined value type `V`, such as the `Value` enum of an interpreter, via [`encode`] and [`decode`].
+/// 
+/// ```
+/// use nanval::{codec::NanCodec, ValueKind};
+/// 
+/// #[derive(Debug, PartialEq)]
+/// enum Value { Number(f64), Int(u64), Other(u64) }
+/// 
+/// impl From<ValueKind> for Value {
+///     fn from(kind: ValueKind) -> Self {
+///         match kind {
+///             ValueKind::Float(x) => Value::Number(x),
+///             ValueKind::Uint(n) => Value::Int(n),
+///             _ => Value::Other(0),
+///         }
+///     }
+/// }
+/// 
+/// impl From<Value> for ValueKind {
+///     fn from(value: Value) -> Self {
+///         match value {
+///             Value::Number(x) => ValueKind::Float(x),
+///             Value::Int(n) => ValueKind::Uint(n),
+///             Value::Other(bits) => ValueKind::Unknown(bits),
+///         }
+///     }
+/// }
+/// 
+/// let bits = NanCodec::encode(Value::Int(42)).unwrap();
+/// assert_eq!(NanCodec::<Value>::decode(bits), Ok(Value::Int(42)));
+/// ```
+pub struct NanCodec<V>(PhantomData<V>);
+
+impl<V: From<ValueKind> + Into<ValueKind>> NanCodec<V> {
+    /// Encodes the given value; returns [`NanError::OutOfRange`] if its data doesn't fit into its kind.
+    pub fn encode(value: V) -> Result<u64, NanError> {
+        encode(value.into()).ok_or(NanError::OutOfRange)
+    }
+    
+    /// Decodes the given bits into a value; returns [`NanError::WrongKind`] if they decode as [`ValueKind::Unknown`].
+    pub fn decode(bits: u64) -> Result<V, NanError> {
+        match decode(bits) {
+            ValueKind::Unknown(_) => Err(NanError::WrongKind),
+            kind => Ok(V::from(kind))
+        }
+    }
+}
+
+#[cfg(test)]
+#[derive(Debug, PartialEq)]
+enum TestValue {
+    Number(f64),
+    Int(u64),
+    #[cfg(feature = "cell")]
+    Object(u64),
+    Raw(u64),
+}
+
+#[cfg(test)]
+impl From<ValueKind> for TestValue {
+    fn from(kind: ValueKind) -> Self {
+        match kind {
+            ValueKind::Float(x) => Self::Number(x),
+            ValueKind::Uint(n) => Self::Int(n),
+            #[cfg(feature = "cell")]
+            ValueKind::Cell { data, .. } => Self::Object(data),
+            ValueKind::Unknown(bits) => Self::Raw(bits),
+        }
+    }
+}
+
+#[cfg(test)]
+impl From<TestValue> for ValueKind {
+    fn from(value: TestValue) -> Self {
+        match value {
+            TestValue::Number(x) => Self::Float(x),
+            TestValue::Int(n) => Self::Uint(n),
+            #[cfg(feature = "cell")]
+            TestValue::Object(data) => Self::Cell { tag: crate::cell::CellTag::Tag4, data },
+            TestValue::Raw(bits) => Self::Unknown(bits),
+        }
+    }
+}
+
+#[test]
+fn test_codec_roundtrip() {
+    type Codec = NanCodec<TestValue>;
+    assert_eq!(Codec::decode(Codec::encode(TestValue::Number(1.5)).unwrap()), Ok(TestValue::Number(1.5)));
+    assert_eq!(Codec::decode(Codec::encode(TestValue::Int(42)).unwrap()), Ok(TestValue::Int(42)));
+    #[cfg(feature = "cell")]
+    assert_eq!(Codec::decode(Codec::encode(TestValue::Object(0x1000)).unwrap()), Ok(TestValue::Object(0x1000)));
+}
+
+#[test]
+fn test_codec_errors() {
+    type Codec = NanCodec<TestValue>;
+    assert_eq!(Codec::encode(TestValue::Int(u64::MAX)), Err(NanError::OutOfRange));
+    let unknown = crate::cons::SIGN_BIT | crate::cons::NAN_BITS | 42;
+    assert_eq!(Codec::encode(TestValue::Raw(unknown)), Ok(unknown));
+    assert_eq!(Codec::decode(unknown), Err(NanError::WrongKind));
+}
@@ -1,17 +1,52 @@
 //! Various important constants for NaN-tagging, bit-masking, etc.
 
+use core::num::NonZeroU64;
+
 /// The maximum integer that can be losslessly stored in an [`f64`] as an [`u64`]: `2 ** 52 - 1`
 pub const MAX_INT: u64 = 9007199254740991;
 
 /// The maximum integer that can be losslessly stored in an [`f64`] as an [`f64`]: `2 ** 52 - 1`
 pub const MAX_INTF: f64 = 9007199254740991.0;
 
+/// The smallest integer that can be stored in a [`crate::sint`]: `-2 ** 47`.
+/// 
+/// **Note:** Unlike [`MAX_INT`], this is *not* `-2 ** 51`; see the [`crate::sint`] module for why.
+pub const MIN_SINT: i64 = -(1 << 47);
+
+/// The largest integer that can be stored in a [`crate::sint`]: `2 ** 47 - 1`.
+pub const MAX_SINT: i64 = (1 << 47) - 1;
+
 /// If this bit-mask matches, the data bits are a [`crate::cell`].
 pub const SIGN_BIT: u64 = 0x8000000000000000;
 
 /// If this bit-mask matches, the value is Not-A-Number / [`f64::NaN`](https://doc.rust-lang.org/std/primitive.f64.html#associatedconstant.NAN).
 pub const NAN_BITS: u64 = 0x7FF8000000000000;
 
+/// The canonical quiet `NaN` bit-pattern that signals a NaN-tagged value; identical to [`NAN_BITS`].
+pub const NAN_SIGNAL: u64 = NAN_BITS;
+
+/// Masks the bits that must match [`NAN_SIGNAL`] for a value to be NaN-tagged: the sign-bit and the [`NAN_BITS`].
+pub const NAN_MASK: u64 = SIGN_BIT | NAN_BITS;
+
+/// Clears the [`NAN_MASK`]-bits of a value, leaving only the payload.
+pub const NAN_UNMASK: u64 = !NAN_MASK;
+
+/// The 'quiet' bit of a `NaN`, which is part of the [`NAN_BITS`].
+pub const QUIET_NAN_BIT: u64 = 0x0008000000000000;
+
+/// The bits of the float `NaN` produced by [`crate::wrap_float`] and [`crate::canonicalize`].
+/// 
+/// By default this is the quiet `NaN`, which is identical to [`NAN_BITS`].
+#[cfg(not(feature = "quiet-nan-is-zero"))]
+pub const CANONICAL_NAN_BITS: u64 = NAN_BITS;
+
+/// The bits of the float `NaN` produced by [`crate::wrap_float`] and [`crate::canonicalize`].
+/// 
+/// With the `quiet-nan-is-zero` feature, a `NaN` is quiet if its [`QUIET_NAN_BIT`] is *clear*,
+/// as on legacy MIPS and PA-RISC; this is their default quiet `NaN`, which never matches the [`NAN_BITS`].
+#[cfg(feature = "quiet-nan-is-zero")]
+pub const CANONICAL_NAN_BITS: u64 = (NAN_BITS & !QUIET_NAN_BIT) | (QUIET_NAN_BIT - 1);
+
 /// This bit-pattern represents positive infinity / [`f64::INFINITY`](https://doc.rust-lang.org/std/primitive.f64.html#associatedconstant.INFINITY).
 pub const POS_INF_BITS: u64 = 0x7FF0000000000000;
 
@@ -20,3 +55,103 @@ pub const NEG_INF_BITS: u64 = 0xFFF0000000000000;
 
 /// This bit-pattern represents negative zero.
 pub const NEG_ZERO_BITS: u64 = 0x8000000000000000;
+
+/// The [`NAN_BITS`] as [`NonZeroU64`], for use in niche-optimized structures.
+pub const NAN_BITS_NONZERO: NonZeroU64 = NonZeroU64::new(NAN_BITS).unwrap();
+
+/// The [`CANONICAL_NAN_BITS`] as [`NonZeroU64`], for use in niche-optimized structures.
+pub const CANONICAL_NAN_BITS_NONZERO: NonZeroU64 = NonZeroU64::new(CANONICAL_NAN_BITS).unwrap();
+
+/// The [`POS_INF_BITS`] as [`NonZeroU64`], for use in niche-optimized structures.
+pub const POS_INF_BITS_NONZERO: NonZeroU64 = NonZeroU64::new(POS_INF_BITS).unwrap();
+
+/// The [`NEG_INF_BITS`] as [`NonZeroU64`], for use in niche-optimized structures.
+pub const NEG_INF_BITS_NONZERO: NonZeroU64 = NonZeroU64::new(NEG_INF_BITS).unwrap();
+
+/// The largest pointer address that can be stored in a cell: `2 ** 48 - 1`.
+pub const MAX_CELL_PTR_ADDR: u64 = 0x0000FFFFFFFFFFFF;
+
+/// The cell-tag bits reserved for singleton values (such as `null`, `true` or `false`); tag `0b001`.
+pub const SINGLETON_TAG_BITS: u64 = 0x0001000000000000;
+
+/// The cell-tag bits reserved for symbols, which store a 48-bit interned id; tag `0b010`.
+pub const SYMBOL_TAG_BITS: u64 = 0x0002000000000000;
+
+/// The cell-tag bits reserved for [`crate::sint`]s, which store a 48-bit signed integer; tag `0b011`.
+pub const SINT_TAG_BITS: u64 = 0x0003000000000000;
+
+/// All cell-tag bits that are reserved by this crate, and thus can't be used to store arbitrary pointers.
+pub const RESERVED_CELL_TAG_BITS: [u64; 3] = [SINGLETON_TAG_BITS, SYMBOL_TAG_BITS, SINT_TAG_BITS];
+
+/// The cell-tag bit that distinguishes pointer-cells from data-cells.
+/// 
+/// By convention, tags `1..=3` (bit clear) are *data-cells*, whose payload is raw data, such as the reserved tags above;
+/// tags `4..=7` (bit set) are *pointer-cells*, whose payload is a pointer that e.g. a garbage collector must trace.
+/// This is why [`crate::cell::from_tag_and_pointer`] rejects the reserved tags.
+pub const POINTER_CELL_TAG_BIT: u64 = 0x0004000000000000;
+
+/// The special float bit-patterns, with a label for each; see [`special_name`].
+pub const SPECIALS: [(u64, &str); 4] = [
+    (CANONICAL_NAN_BITS, "NaN"),
+    (POS_INF_BITS, "+inf"),
+    (NEG_INF_BITS, "-inf"),
+    (NEG_ZERO_BITS, "-0"),
+];
+
+/// Returns the label of the given bits, if they are one of the [`SPECIALS`].
+pub fn special_name(bits: u64) -> Option<&'static str> {
+    SPECIALS.iter().find(|(special, _)| *special == bits).map(|(_, name)| *name)
+}
+
+// Compile-time checks of the invariants above, so a typo fails `cargo build` and not just `cargo test`.
+const _: () = {
+    assert!(NEG_INF_BITS == SIGN_BIT | POS_INF_BITS);
+    assert!(NEG_ZERO_BITS == SIGN_BIT);
+    assert!(NAN_BITS != POS_INF_BITS);
+    assert!(NAN_BITS & POS_INF_BITS == POS_INF_BITS);
+    assert!(NAN_BITS & SIGN_BIT == 0);
+    assert!(NAN_MASK & NAN_SIGNAL == NAN_SIGNAL);
+    assert!(NAN_MASK & NAN_UNMASK == 0);
+    assert!(NAN_BITS == POS_INF_BITS | QUIET_NAN_BIT);
+    assert!(CANONICAL_NAN_BITS & POS_INF_BITS == POS_INF_BITS && CANONICAL_NAN_BITS != POS_INF_BITS);
+    assert!(CANONICAL_NAN_BITS & SIGN_BIT == 0);
+    assert!(SINGLETON_TAG_BITS & POINTER_CELL_TAG_BIT == 0);
+    assert!(SYMBOL_TAG_BITS & POINTER_CELL_TAG_BIT == 0);
+    assert!(SINT_TAG_BITS & POINTER_CELL_TAG_BIT == 0);
+    assert!(MAX_INTF as u64 == MAX_INT);
+};
+
+#[test]
+fn test_nonzero_constants() {
+    assert_eq!(NAN_BITS_NONZERO.get(), NAN_BITS);
+    assert_eq!(CANONICAL_NAN_BITS_NONZERO.get(), CANONICAL_NAN_BITS);
+    assert_eq!(POS_INF_BITS_NONZERO.get(), POS_INF_BITS);
+    assert_eq!(NEG_INF_BITS_NONZERO.get(), NEG_INF_BITS);
+}
+
+#[test]
+fn test_special_name() {
+    assert_eq!(special_name(POS_INF_BITS), Some("+inf"));
+    assert_eq!(special_name(NEG_INF_BITS), Some("-inf"));
+    assert_eq!(special_name(NEG_ZERO_BITS), Some("-0"));
+    assert_eq!(special_name(CANONICAL_NAN_BITS), Some("NaN"));
+    assert_eq!(special_name(1.5f64.to_bits()), None);
+}
+
+#[test]
+fn test_uint_stays_nan() {
+    use crate::uint::{wrap_unchecked, UINT_DATA_BITS};
+    const EXPONENT_BITS: u64 = POS_INF_BITS;
+    
+    // The payload only ever sets mantissa bits below the quiet bit,
+    // so the exponent stays all-ones and the mantissa non-zero; never an infinity.
+    for payload in [0, 1, UINT_DATA_BITS] {
+        let bits = wrap_unchecked(payload);
+        assert_eq!(bits & EXPONENT_BITS, EXPONENT_BITS);
+        assert_eq!(bits & SIGN_BIT, 0);
+        assert_ne!(bits & !(SIGN_BIT | EXPONENT_BITS), 0);
+        assert!(f64::from_bits(bits).is_nan() && f64::from_bits(bits).is_sign_positive());
+    }
+    assert_eq!(wrap_unchecked(0), NAN_BITS);
+    assert_eq!(UINT_DATA_BITS & (SIGN_BIT | EXPONENT_BITS | QUIET_NAN_BIT), 0);
+}
@@ -0,0 +1,39 @@
+//! The error type of the fallible functions in this crate.
+
+/// Errors that can occur when creating or handling NaN-tagged values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum NanError {
+    /// The value doesn't fit into the data bits of the requested kind.
+    OutOfRange,
+    
+    /// An arithmetic operation overflowed the data bits of the result.
+    Overflow,
+    
+    /// The value is not of the kind required by the operation.
+    WrongKind,
+    
+    /// A handle refers to a slot that has since been reused; see `cell::validate_handle`.
+    StaleHandle,
+    
+    /// A literal could not be parsed, due to the character at the given byte-position.
+    InvalidSyntax {
+        /// Byte-position of the offending character.
+        position: usize
+    },
+}
+
+impl core::fmt::Display for NanError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfRange => f.write_str("value does not fit into the data bits"),
+            Self::Overflow => f.write_str("arithmetic overflow"),
+            Self::WrongKind => f.write_str("value is of the wrong kind"),
+            Self::StaleHandle => f.write_str("handle refers to a reused slot"),
+            Self::InvalidSyntax { position } => write!(f, "invalid syntax at position {position}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NanError {}
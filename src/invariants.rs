@@ -0,0 +1,51 @@
+//! Invariants that are shared between the tests and the fuzz targets.
+//! 
+//! Only compiled for tests, or with `--cfg fuzzing`.
+
+use super::{decode, encode, ValueKind};
+
+/// Asserts that decoding the given word and encoding it again reproduces the exact same bits.
+/// 
+/// There are two documented exceptions:
+/// - Float `NaN`s only have to stay `NaN`s, as [`encode`] canonicalizes their payload.
+/// - Pointer-cells are skipped, as their payload is an address and not data.
+pub fn assert_roundtrip(word: u64) {
+    let kind = decode(word);
+    match kind {
+        ValueKind::Float(value) if value.is_nan() => {
+            let encoded = encode(kind).expect("a float always encodes");
+            assert!(matches!(decode(encoded), ValueKind::Float(x) if x.is_nan()), "{word:#018x} didn't stay a NaN");
+            return
+        },
+        #[cfg(feature = "cell")]
+        ValueKind::Cell { .. } if crate::cell::is_pointer_cell(word) => return,
+        _ => {}
+    }
+    assert_eq!(encode(kind), Some(word), "{word:#018x} didn't roundtrip as {kind:?}");
+}
+
+#[test]
+fn test_assert_roundtrip() {
+    use super::cons::*;
+    let words = [
+        0,
+        1.5f64.to_bits(),
+        NEG_ZERO_BITS,
+        POS_INF_BITS,
+        NEG_INF_BITS,
+        CANONICAL_NAN_BITS,
+        POS_INF_BITS | 1,
+        SIGN_BIT | NAN_BITS,
+        crate::uint::wrap_unchecked(1),
+        crate::uint::wrap_unchecked(crate::uint::MAX_VALUE),
+        SIGN_BIT | NAN_BITS | 42,
+    ];
+    for word in words {
+        assert_roundtrip(word);
+    }
+    #[cfg(feature = "cell")]
+    for index in 1..=7 {
+        let tag = crate::cell::CellTag::from_u8(index).unwrap();
+        assert_roundtrip(crate::cell::from_tag_and_data(tag, crate::cell::CELL_DATA_BITS).unwrap());
+    }
+}
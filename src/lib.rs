@@ -2,25 +2,94 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+#[cfg(any(feature = "std", feature = "alloc"))]
+extern crate alloc;
+
+pub mod array;
+pub mod codec;
 pub mod cons;
+pub mod error;
+pub mod parse;
 pub mod raw;
+pub mod rawval;
+pub mod registry;
+pub mod sint;
+pub mod slice;
 pub mod uint;
 
 #[cfg(feature = "cell")]
 pub mod cell;
 
+#[cfg(feature = "cell")]
+pub mod visit;
+
+/// The former name of the [`rawval`] module.
+#[deprecated(note = "renamed to `rawval`")]
+pub mod old {
+    pub use super::rawval::*;
+}
+
+#[cfg(feature = "std")]
+pub mod cache;
+
+#[cfg(feature = "alloc")]
+pub mod alloc_impl;
+
+#[cfg(all(feature = "cell", any(feature = "std", feature = "alloc")))]
+pub mod simd;
+
+#[cfg(any(test, fuzzing))]
+pub mod invariants;
+
+#[cfg(feature = "serde")]
+pub mod serde_impl;
+
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary_impl;
+
+#[cfg(feature = "speedy")]
+pub mod speedy_impl;
+
 pub use raw::IntoRawBits64;
+pub use rawval::RawNaNVal;
+pub use error::NanError;
 use cons::*;
 
+/// Constructs the bits of a NaN-tagged value, panicking on invalid input.
+///
+/// - `nanval!(1.5f64)` returns the raw bits of the expression.
+/// - `nanval!(uint 42)` returns a uint, via [`uint::wrap`].
+/// - `nanval!(cell Tag4, ptr)` returns a cell, via [`cell::from_tag_and_pointer`].
+#[macro_export]
+macro_rules! nanval {
+    (uint $value:expr) => {
+        $crate::uint::wrap($value).unwrap()
+    };
+    (cell $tag:ident, $ptr:expr) => {
+        $crate::cell::from_tag_and_pointer($crate::cell::CellTag::$tag, $ptr).unwrap()
+    };
+    ($value:expr) => {
+        $crate::IntoRawBits64::as_raw_bits_64(&$value)
+    };
+}
+
 /// Checks if the given value is a valid `f64`.
 /// 
 /// There are exactly two cases where this is true:
 /// - If the values bits masked with [`NAN_BITS`] are NOT [`NAN_BITS`].
-/// - If the values bits EXACTLY match [`NAN_BITS`].
+/// - If the values bits EXACTLY match [`CANONICAL_NAN_BITS`].
+/// 
+/// With the `quiet-nan-is-zero` feature, the canonical `NaN` never matches the [`NAN_BITS`],
+/// so the [`NAN_BITS`] themselves are the uint `0` instead of a float.
 #[inline(always)]
 pub fn is_float(value: impl IntoRawBits64) -> bool {
-    let value = value.as_raw_bits_64();
-    value & NAN_BITS != NAN_BITS || value == NAN_BITS
+    is_float_u64(value.as_raw_bits_64())
+}
+
+/// Checks if the given bits are a valid `f64`; the `const` version of [`is_float`].
+#[inline(always)]
+pub const fn is_float_u64(value: u64) -> bool {
+    value & NAN_BITS != NAN_BITS || value == CANONICAL_NAN_BITS
 }
 
 /// Checks if the given value is a NaN-tagged value.
@@ -28,7 +97,13 @@ pub fn is_float(value: impl IntoRawBits64) -> bool {
 /// Calls [`is_float`] and negates the result.
 #[inline(always)]
 pub fn is_nanval(value: impl IntoRawBits64) -> bool {
-    ! is_float(value)
+    is_nanval_u64(value.as_raw_bits_64())
+}
+
+/// Checks if the given bits are a NaN-tagged value; the `const` version of [`is_nanval`].
+#[inline(always)]
+pub const fn is_nanval_u64(value: u64) -> bool {
+    ! is_float_u64(value)
 }
 
 /// Returns the value as `f64`; does *not* check if the value is actually a float.
@@ -45,3 +120,871 @@ pub fn unwrap_float(value: impl IntoRawBits64) -> Option<f64> {
         false => None
     }
 }
+
+/// Returns the bits of the given `f64`, turning any `NaN` into [`CANONICAL_NAN_BITS`].
+/// 
+/// This ensures that the result is always a valid `f64` and never mistaken for a NaN-tagged value.
+#[inline(always)]
+pub fn wrap_float(value: f64) -> u64 {
+    match value.is_nan() {
+        true => CANONICAL_NAN_BITS,
+        false => value.to_bits()
+    }
+}
+
+/// Overwrites the given slot with the given `f64`, but *only* if the slot currently holds a valid `f64`.
+/// 
+/// Returns wether the slot was updated; a slot holding a NaN-tagged value is left untouched.
+#[inline(always)]
+pub fn update_float(slot: &mut u64, value: f64) -> bool {
+    match is_float(*slot) {
+        true => {
+            *slot = wrap_float(value);
+            true
+        },
+        false => false
+    }
+}
+
+/// Returns the value as `f64` if it is a valid 64-bit floating point number, or the given `default` otherwise.
+#[inline(always)]
+pub fn unwrap_float_or(value: impl IntoRawBits64, default: f64) -> f64 {
+    unwrap_float(value).unwrap_or(default)
+}
+
+#[test]
+fn test_unwrap_float_or() {
+    assert_eq!(unwrap_float_or(1.5f64, 0.0), 1.5);
+    assert_eq!(unwrap_float_or(uint::UINT_MARKER_BITS | 42, 0.0), 0.0);
+    #[cfg(feature = "cell")]
+    {
+        let cell = cell::from_tag_and_data(cell::CellTag::Tag4, 42).unwrap();
+        assert_eq!(unwrap_float_or(cell, -1.0), -1.0);
+    }
+}
+
+/// Returns the minimum of the given values, if both of them are valid `f64`s.
+/// 
+/// Follows [`f64::min`]: if one of the values is `NaN`, the other one is returned.
+/// 
+/// If either value is a NaN-tagged value, the values are of different kinds and `None` is returned.
+#[inline(always)]
+pub fn float_min(a: impl IntoRawBits64, b: impl IntoRawBits64) -> Option<f64> {
+    Some(unwrap_float(a)?.min(unwrap_float(b)?))
+}
+
+/// Returns the maximum of the given values, if both of them are valid `f64`s.
+/// 
+/// Follows [`f64::max`]: if one of the values is `NaN`, the other one is returned.
+/// 
+/// If either value is a NaN-tagged value, the values are of different kinds and `None` is returned.
+#[inline(always)]
+pub fn float_max(a: impl IntoRawBits64, b: impl IntoRawBits64) -> Option<f64> {
+    Some(unwrap_float(a)?.max(unwrap_float(b)?))
+}
+
+#[test]
+fn test_float_min_max() {
+    assert_eq!(float_min(1.0f64, 2.0f64), Some(1.0));
+    assert_eq!(float_max(1.0f64, 2.0f64), Some(2.0));
+    let nan = unwrap_float_unchecked(CANONICAL_NAN_BITS);
+    assert_eq!(float_min(nan, 2.0f64), Some(2.0));
+    assert_eq!(float_max(-2.0f64, nan), Some(-2.0));
+    assert!(float_min(nan, nan).is_some_and(f64::is_nan));
+    
+    let uint = uint::UINT_MARKER_BITS | 42;
+    assert_eq!(float_min(uint, 2.0f64), None);
+    assert_eq!(float_max(2.0f64, uint), None);
+}
+
+/// Returns the canonical form of the given value, so that numerically equal floats have identical bits.
+/// 
+/// - Any float `NaN` becomes [`CANONICAL_NAN_BITS`].
+/// - `-0.0` becomes `+0.0`.
+/// - The negated `NaN` (`SIGN_BIT | NAN_BITS`), as produced by some hardware for `0.0 / 0.0`, becomes [`CANONICAL_NAN_BITS`].
+/// 
+/// All other values, including every NaN-tagged value, are returned as-is.
+#[inline(always)]
+pub fn canonicalize(value: impl IntoRawBits64) -> u64 {
+    let bits = value.as_raw_bits_64();
+    if bits == NEG_ZERO_BITS {
+        return 0
+    }
+    if bits == SIGN_BIT | NAN_BITS || (is_float(bits) && unwrap_float_unchecked(bits).is_nan()) {
+        return CANONICAL_NAN_BITS
+    }
+    bits
+}
+
+/// Replaces every word of the given buffer with its [canonical form](canonicalize), in place.
+/// 
+/// Only floats (and the negated `NaN`) change; uints and cells are left untouched.
+#[inline]
+pub fn canonicalize_all(words: &mut [u64]) {
+    for word in words {
+        *word = canonicalize(*word);
+    }
+}
+
+#[test]
+fn test_canonicalize_all() {
+    let uint = uint::wrap_unchecked(42);
+    let mut words = [(-f64::NAN).to_bits(), NEG_ZERO_BITS, 1.5f64.to_bits(), uint];
+    canonicalize_all(&mut words);
+    assert_eq!(words, [CANONICAL_NAN_BITS, 0, 1.5f64.to_bits(), uint]);
+    #[cfg(feature = "cell")]
+    {
+        let cell = cell::from_tag_and_data(cell::CellTag::Tag4, 42).unwrap();
+        let mut words = [cell, NEG_ZERO_BITS];
+        canonicalize_all(&mut words);
+        assert_eq!(words, [cell, 0]);
+    }
+}
+
+/// Hashes the [canonical form](canonicalize) of the given value, using the [`DefaultHasher`](std::collections::hash_map::DefaultHasher).
+/// 
+/// **Note:** The hash is only stable within a single build, as the algorithm of the default hasher may change between Rust releases.
+#[cfg(feature = "std")]
+pub fn hash_one(value: impl IntoRawBits64) -> u64 {
+    use std::hash::{BuildHasher, BuildHasherDefault};
+    use std::collections::hash_map::DefaultHasher;
+    BuildHasherDefault::<DefaultHasher>::default().hash_one(canonicalize(value))
+}
+
+#[test]
+fn test_canonicalize() {
+    assert_eq!(canonicalize(CANONICAL_NAN_BITS), CANONICAL_NAN_BITS);
+    assert_eq!(canonicalize(SIGN_BIT | NAN_BITS), CANONICAL_NAN_BITS);
+    assert_eq!(canonicalize(POS_INF_BITS | 1), CANONICAL_NAN_BITS);
+    assert_eq!(canonicalize(NEG_INF_BITS | 1), CANONICAL_NAN_BITS);
+    assert_eq!(canonicalize(-0.0f64), 0);
+    assert_eq!(canonicalize(1.5f64), 1.5f64.to_bits());
+    assert_eq!(canonicalize(NEG_INF_BITS), NEG_INF_BITS);
+    assert_eq!(canonicalize(uint::UINT_MARKER_BITS | 42), uint::UINT_MARKER_BITS | 42);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_hash_one() {
+    let nan = hash_one(CANONICAL_NAN_BITS);
+    assert_eq!(hash_one(SIGN_BIT | NAN_BITS), nan);
+    assert_eq!(hash_one(POS_INF_BITS | 1), nan);
+    assert_eq!(hash_one(-0.0f64), hash_one(0.0f64));
+    assert_eq!(hash_one(1.5f64), hash_one(1.5f64.to_bits()));
+    assert_ne!(hash_one(1.5f64), hash_one(2.5f64));
+}
+
+/// Returns the data bits of the given value, whatever kind of NaN-tagged value it is.
+/// 
+/// - For a [`uint`], the [`uint::UINT_DATA_BITS`] are returned.
+/// - For a [`cell`], the [`cell::CELL_DATA_BITS`] are returned.
+/// - For a valid `f64` (including [`CANONICAL_NAN_BITS`]), `None` is returned.
+#[inline(always)]
+pub fn payload(value: impl IntoRawBits64) -> Option<u64> {
+    if is_float(value) {
+        return None
+    }
+    if let Some(data) = uint::unwrap_uint(value) {
+        return Some(data)
+    }
+    #[cfg(feature = "cell")]
+    if let Some(data) = cell::unwrap_cell(value) {
+        return Some(data)
+    }
+    None
+}
+
+#[test]
+fn test_payload() {
+    assert_eq!(payload(1.5f64), None);
+    assert_eq!(payload(CANONICAL_NAN_BITS), None);
+    assert_eq!(payload(uint::UINT_MARKER_BITS | uint::UINT_DATA_BITS), Some(uint::UINT_DATA_BITS));
+    #[cfg(feature = "cell")]
+    {
+        let cell = cell::from_tag_and_data(cell::CellTag::Tag7, cell::CELL_DATA_BITS).unwrap();
+        assert_eq!(payload(cell), Some(cell::CELL_DATA_BITS));
+    }
+}
+
+/// Returns wether the given value is a signaling `NaN`; a `NaN` whose [`QUIET_NAN_BIT`] differs from the [`CANONICAL_NAN_BITS`].
+/// 
+/// By default that is a `NaN` with a *clear* quiet bit, as specified by IEEE 754-2008;
+/// with the `quiet-nan-is-zero` feature, it's a `NaN` with a *set* quiet bit instead.
+/// 
+/// **Note:** By default, the NaN-tagged values of this crate are never signaling `NaN`s;
+/// with the `quiet-nan-is-zero` feature, they all look like signaling `NaN`s to the FPU.
+#[inline(always)]
+pub fn is_signaling_nan(value: impl IntoRawBits64) -> bool {
+    let bits = value.as_raw_bits_64() & !SIGN_BIT;
+    bits > POS_INF_BITS && (bits & QUIET_NAN_BIT) != (CANONICAL_NAN_BITS & QUIET_NAN_BIT)
+}
+
+/// Returns the 51-bit payload of the given value, if it is a signaling `NaN`; see [`is_signaling_nan`].
+#[inline(always)]
+pub fn nan_payload(value: impl IntoRawBits64) -> Option<u64> {
+    match is_signaling_nan(value) {
+        true => Some(value.as_raw_bits_64() & (QUIET_NAN_BIT - 1)),
+        false => None
+    }
+}
+
+#[test]
+#[cfg(not(feature = "quiet-nan-is-zero"))]
+fn test_signaling_nan() {
+    // The smallest and largest signaling NaNs, per IEEE 754-2008.
+    assert!(is_signaling_nan(0x7FF0000000000001u64));
+    assert!(is_signaling_nan(0x7FF7FFFFFFFFFFFFu64));
+    assert!(is_signaling_nan(0xFFF4000000000000u64));
+    assert_eq!(nan_payload(0x7FF0000000000001u64), Some(1));
+    assert_eq!(nan_payload(0xFFF7FFFFFFFFFFFFu64), Some(QUIET_NAN_BIT - 1));
+    
+    // Quiet NaNs, infinities and NaN-tagged values.
+    assert!(!is_signaling_nan(0x7FF8000000000000u64));
+    assert!(!is_signaling_nan(0xFFF8000000000001u64));
+    assert!(!is_signaling_nan(POS_INF_BITS) && !is_signaling_nan(NEG_INF_BITS));
+    assert!(!is_signaling_nan(1.5f64));
+    assert!(!is_signaling_nan(uint::wrap_unchecked(42)));
+    assert_eq!(nan_payload(CANONICAL_NAN_BITS), None);
+}
+
+#[test]
+#[cfg(feature = "quiet-nan-is-zero")]
+fn test_signaling_nan_quiet_is_zero() {
+    assert!(is_signaling_nan(0x7FF8000000000001u64));
+    assert_eq!(nan_payload(0x7FF8000000000001u64), Some(1));
+    assert!(!is_signaling_nan(0x7FF0000000000001u64));
+    assert!(!is_signaling_nan(CANONICAL_NAN_BITS));
+    assert!(!is_signaling_nan(POS_INF_BITS));
+}
+
+/// The kind of a value, as returned by [`decode`].
+#[derive(Clone, Copy, Debug)]
+pub enum ValueKind {
+    /// A valid `f64`.
+    Float(f64),
+    /// A [`uint`], with its data bits.
+    Uint(u64),
+    /// A [`cell`], with its tag and data bits.
+    #[cfg(feature = "cell")]
+    Cell {
+        /// The tag of the cell.
+        tag: cell::CellTag,
+        /// The data bits of the cell.
+        data: u64
+    },
+    /// A NaN-tagged value that isn't recognized, with its raw bits.
+    Unknown(u64),
+}
+
+/// Decodes the given value into its [`ValueKind`].
+/// 
+/// The kinds are checked in a fixed order, so that every value decodes to exactly one kind:
+/// 1. [`is_float`], which includes [`CANONICAL_NAN_BITS`] and thus, by default, the uint `0`.
+/// 2. [`uint::is_uint`].
+/// 3. [`cell::unwrap_tag`], so a cell with the undefined tag `0` is *not* a cell.
+/// 4. Anything else, such as a cell with tag `0`, is [`ValueKind::Unknown`].
+#[inline(always)]
+pub fn decode(value: impl IntoRawBits64) -> ValueKind {
+    let bits = value.as_raw_bits_64();
+    // The marker bits are masked once; every kind is derived from them.
+    let marker = bits & NAN_MASK;
+    if marker & NAN_BITS != NAN_BITS || bits == CANONICAL_NAN_BITS {
+        return ValueKind::Float(f64::from_bits(bits))
+    }
+    if marker == uint::UINT_MARKER_BITS {
+        return ValueKind::Uint(bits & uint::UINT_DATA_BITS)
+    }
+    // Neither a float nor a uint, so the marker must be the cell marker.
+    #[cfg(feature = "cell")]
+    if let Ok(tag) = cell::CellTag::try_from(bits & cell::CELL_TAG_BITS) {
+        return ValueKind::Cell { tag, data: bits & cell::CELL_DATA_BITS }
+    }
+    ValueKind::Unknown(bits)
+}
+
+/// Encodes the given [`ValueKind`] into the bits of a value; the inverse of [`decode`].
+/// 
+/// Returns `None` if the data doesn't fit into the given kind.
+/// A float `NaN` is encoded as the [`CANONICAL_NAN_BITS`], see [`wrap_float`].
+#[inline(always)]
+pub fn encode(kind: ValueKind) -> Option<u64> {
+    match kind {
+        ValueKind::Float(value) => Some(wrap_float(value)),
+        ValueKind::Uint(data) => uint::wrap(data),
+        #[cfg(feature = "cell")]
+        ValueKind::Cell { tag, data } => cell::from_tag_and_data(tag, data),
+        ValueKind::Unknown(bits) => Some(bits),
+    }
+}
+
+#[test]
+fn test_decode() {
+    assert!(matches!(decode(1.5f64), ValueKind::Float(x) if x == 1.5));
+    assert!(matches!(decode(CANONICAL_NAN_BITS), ValueKind::Float(x) if x.is_nan()));
+    assert!(matches!(decode(uint::wrap_unchecked(42)), ValueKind::Uint(42)));
+    assert!(matches!(decode(NAN_BITS | 1), ValueKind::Uint(1)));
+    #[cfg(feature = "cell")]
+    {
+        let cell = cell::from_tag_and_data(cell::CellTag::Tag5, 0xBEEF).unwrap();
+        assert!(matches!(decode(cell), ValueKind::Cell { tag: cell::CellTag::Tag5, data: 0xBEEF }));
+        assert!(matches!(decode(cell::CELL_MARKER_BITS | 1), ValueKind::Unknown(_)));
+    }
+    #[cfg(not(feature = "cell"))]
+    assert!(matches!(decode(SIGN_BIT | NAN_BITS | 1), ValueKind::Unknown(_)));
+}
+
+#[test]
+fn test_decode_agrees_with_predicates() {
+    let mut samples = [0u64; 64];
+    for (i, sample) in samples.iter_mut().enumerate() {
+        // Walks over every combination of sign, NaN-ness and tag, with and without data.
+        let i = i as u64;
+        let sign = (i & 1) << 63;
+        let nan = match (i >> 1) & 1 { 1 => NAN_BITS, _ => 1.5f64.to_bits() };
+        let tag = ((i >> 2) & 0x7) << 48;
+        let data = (i >> 5) * 0x0000_1234_5678_9ABC;
+        *sample = sign | nan | tag | data;
+    }
+    let specials = [0, NAN_BITS, CANONICAL_NAN_BITS, SIGN_BIT | NAN_BITS, NEG_ZERO_BITS, POS_INF_BITS, NEG_INF_BITS, uint::wrap_unchecked(1)];
+    for bits in samples.into_iter().chain(specials) {
+        match decode(bits) {
+            ValueKind::Float(x) => {
+                assert!(is_float(bits));
+                assert_eq!(x.to_bits(), bits);
+            },
+            ValueKind::Uint(data) => {
+                assert!(!is_float(bits) && uint::is_uint(bits));
+                assert_eq!(uint::unwrap_uint(bits), Some(data));
+            },
+            #[cfg(feature = "cell")]
+            ValueKind::Cell { tag, data } => {
+                assert!(!is_float(bits) && !uint::is_uint(bits) && cell::is_cell(bits));
+                assert_eq!(cell::unwrap_tag(bits).map(|t| t as u64), Some(tag as u64));
+                assert_eq!(cell::unwrap_cell(bits), Some(data));
+            },
+            ValueKind::Unknown(raw) => {
+                assert!(!is_float(bits) && !uint::is_uint(bits));
+                #[cfg(feature = "cell")]
+                assert!(cell::unwrap_tag(bits).is_none());
+                assert_eq!(raw, bits);
+            },
+        }
+    }
+}
+
+#[test]
+fn test_update_float() {
+    let mut slot = 1.5f64.to_bits();
+    assert!(update_float(&mut slot, 2.5));
+    assert_eq!(slot, 2.5f64.to_bits());
+    assert!(update_float(&mut slot, -f64::NAN));
+    assert_eq!(slot, CANONICAL_NAN_BITS);
+    
+    let mut slot = uint::UINT_MARKER_BITS | 42;
+    assert!(!update_float(&mut slot, 2.5));
+    assert_eq!(slot, uint::UINT_MARKER_BITS | 42);
+    #[cfg(feature = "cell")]
+    {
+        let cell = cell::from_tag_and_data(cell::CellTag::Tag4, 42).unwrap();
+        let mut slot = cell;
+        assert!(!update_float(&mut slot, 2.5));
+        assert_eq!(slot, cell);
+    }
+}
+
+/// Returns the value as `i64`, if it is a valid `f64` holding an integer that is exactly representable as `i64`.
+#[inline(always)]
+pub fn float_as_exact_int(value: impl IntoRawBits64) -> Option<i64> {
+    let f = unwrap_float(value)?;
+    // `i64::MIN` is exactly representable as `f64`, but `i64::MAX` is not; hence the half-open range.
+    if !(-9223372036854775808.0..9223372036854775808.0).contains(&f) {
+        return None
+    }
+    let n = f as i64;
+    (n as f64 == f).then_some(n)
+}
+
+#[test]
+fn test_float_as_exact_int() {
+    assert_eq!(float_as_exact_int(3.0f64), Some(3));
+    assert_eq!(float_as_exact_int(-3.0f64), Some(-3));
+    assert_eq!(float_as_exact_int(-0.0f64), Some(0));
+    assert_eq!(float_as_exact_int(3.5f64), None);
+    assert_eq!(float_as_exact_int(9007199254740992.0f64), Some(1 << 53));
+    assert_eq!(float_as_exact_int(i64::MIN as f64), Some(i64::MIN));
+    assert_eq!(float_as_exact_int(9223372036854775808.0f64), None);
+    assert_eq!(float_as_exact_int(1e300f64), None);
+    assert_eq!(float_as_exact_int(f64::INFINITY), None);
+    assert_eq!(float_as_exact_int(f64::NAN), None);
+    assert_eq!(float_as_exact_int(uint::UINT_MARKER_BITS | 3), None);
+}
+
+/// A NaN-tagged value whose [`Hash`](core::hash::Hash) and [`Eq`] operate on its [canonical form](canonicalize).
+/// 
+/// Numerically equal floats, such as `-0.0` and `+0.0` or any two `NaN`s, are thus considered the same;
+/// this makes e.g. a `HashSet<HashableValue>` hold each numeric value only once.
+#[derive(Clone, Copy, Debug)]
+pub struct HashableValue(pub u64);
+
+impl HashableValue {
+    /// Wraps the given value.
+    #[inline(always)]
+    pub fn new(value: impl IntoRawBits64) -> Self {
+        Self(value.as_raw_bits_64())
+    }
+}
+
+impl PartialEq for HashableValue {
+    fn eq(&self, other: &Self) -> bool {
+        canonicalize(self.0) == canonicalize(other.0)
+    }
+}
+
+impl Eq for HashableValue {}
+
+impl core::hash::Hash for HashableValue {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        canonicalize(self.0).hash(state)
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_hashable_value() {
+    let mut set = std::collections::HashSet::new();
+    set.insert(HashableValue::new(0.0f64));
+    set.insert(HashableValue::new(-0.0f64));
+    assert_eq!(set.len(), 1);
+    
+    set.insert(HashableValue::new(CANONICAL_NAN_BITS));
+    set.insert(HashableValue::new(SIGN_BIT | NAN_BITS));
+    assert_eq!(set.len(), 2);
+    
+    set.insert(HashableValue::new(uint::wrap_unchecked(1)));
+    set.insert(HashableValue::new(uint::wrap_unchecked(2)));
+    assert_eq!(set.len(), 4);
+}
+
+/// A NaN-tagged value, with the free functions of this crate as methods.
+/// 
+/// Equality and hashing operate on the raw bits; use [`HashableValue`] to compare numerically.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct NanVal(u64);
+
+impl NanVal {
+    /// The `null` singleton; see [`cell::NULL`].
+    #[cfg(feature = "cell")]
+    pub const NULL: Self = Self(cell::NULL);
+    
+    /// The `undefined` singleton; see [`cell::UNDEFINED`].
+    #[cfg(feature = "cell")]
+    pub const UNDEFINED: Self = Self(cell::UNDEFINED);
+    
+    /// The error singleton, as returned by a division by zero; see [`cell::ERROR`].
+    #[cfg(feature = "cell")]
+    pub const ERROR: Self = Self(cell::ERROR);
+    
+    /// Checks if the value is a valid `f64`; see [`is_float`].
+    #[inline(always)]
+    pub fn is_float(&self) -> bool {
+        is_float(self.0)
+    }
+    
+    /// Checks if the value is a NaN-tagged value; see [`is_nanval`].
+    #[inline(always)]
+    pub fn is_nanval(&self) -> bool {
+        is_nanval(self.0)
+    }
+    
+    /// Returns the value as `f64`, if it is one; see [`unwrap_float`].
+    #[inline(always)]
+    pub fn as_float(&self) -> Option<f64> {
+        unwrap_float(self.0)
+    }
+    
+    /// Checks if the value is a uint; see [`uint::is_uint`].
+    #[inline(always)]
+    pub fn is_uint(&self) -> bool {
+        uint::is_uint(self.0)
+    }
+    
+    /// Returns the data of the uint, if the value is one; see [`uint::unwrap_uint`].
+    #[inline(always)]
+    pub fn as_uint(&self) -> Option<u64> {
+        uint::unwrap_uint(self.0)
+    }
+    
+    /// Checks if the value is a sint; see [`sint::is_sint`].
+    #[inline(always)]
+    pub fn is_sint(&self) -> bool {
+        sint::is_sint(self.0)
+    }
+    
+    /// Returns the sint, if the value is one; see [`sint::unwrap_sint`].
+    #[inline(always)]
+    pub fn as_sint(&self) -> Option<i64> {
+        sint::unwrap_sint(self.0)
+    }
+    
+    /// Checks if the value is a cell; see [`cell::is_cell`].
+    #[cfg(feature = "cell")]
+    #[inline(always)]
+    pub fn is_cell(&self) -> bool {
+        cell::is_cell(self.0)
+    }
+    
+    /// Returns the tag of the cell, if the value is one; see [`cell::unwrap_tag`].
+    #[cfg(feature = "cell")]
+    #[inline(always)]
+    pub fn tag(&self) -> Option<cell::CellTag> {
+        cell::unwrap_tag(self.0)
+    }
+    
+    /// Returns the data of the cell, if the value is one; see [`cell::unwrap_cell`].
+    #[cfg(feature = "cell")]
+    #[inline(always)]
+    pub fn as_cell(&self) -> Option<u64> {
+        cell::unwrap_cell(self.0)
+    }
+    
+    /// Returns the pointer of the cell, if the value is a cell with the isolated tag bits `TAG`, one of [`cell::CELL_TAG_4`]`..=`[`cell::CELL_TAG_7`].
+    /// 
+    /// Any other `TAG` is a compile-time error, as only pointer-cells hold pointers:
+    /// ```compile_fail
+    /// # use nanval::{cell, NanVal};
+    /// NanVal::NULL.as_ptr_if_tag::<{cell::CELL_TAG_1}>();
+    /// ```
+    /// 
+    /// **Note:** The returned pointer may be invalid, as with [`cell::unwrap_cell_rawptr`].
+    #[cfg(feature = "cell")]
+    #[inline(always)]
+    pub fn as_ptr_if_tag<const TAG: u64>(self) -> Option<*const ()> {
+        const {
+            assert!(
+                TAG & !cell::CELL_TAG_BITS == 0 && TAG & POINTER_CELL_TAG_BIT != 0,
+                "`TAG` must be one of `CELL_TAG_4..=CELL_TAG_7`"
+            )
+        };
+        match cell::is_cell_with_tag_u64(self.0, TAG) {
+            true => Some((self.0 & cell::CELL_DATA_BITS) as *const ()),
+            false => None
+        }
+    }
+    
+    /// Checks if the value is the `null` singleton; see [`cell::is_null`].
+    #[cfg(feature = "cell")]
+    #[inline(always)]
+    pub fn is_null(&self) -> bool {
+        cell::is_null(self.0)
+    }
+    
+    /// Checks if the value is the `undefined` singleton; see [`cell::is_undefined`].
+    #[cfg(feature = "cell")]
+    #[inline(always)]
+    pub fn is_undefined(&self) -> bool {
+        cell::is_undefined(self.0)
+    }
+    
+    /// Returns the data bits, whatever kind of NaN-tagged value it is; see [`payload`].
+    #[inline(always)]
+    pub fn payload(&self) -> Option<u64> {
+        payload(self.0)
+    }
+    
+    /// Decodes the value into its [`ValueKind`]; see [`decode`].
+    #[inline(always)]
+    pub fn decode(&self) -> ValueKind {
+        decode(self.0)
+    }
+    
+    /// Returns the canonical form of the value; see [`canonicalize`].
+    #[inline(always)]
+    pub fn canonicalize(self) -> Self {
+        Self(canonicalize(self.0))
+    }
+}
+
+impl IntoRawBits64 for NanVal {
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Wraps the given bits as-is.
+impl From<u64> for NanVal {
+    fn from(bits: u64) -> Self {
+        Self(bits)
+    }
+}
+
+/// Wraps the given integer as a uint, via [`uint::from_u32`]; this always succeeds.
+/// 
+/// **Note:** Unlike `From<u64>`, which takes the raw bits of a value, this creates a uint.
+impl From<u32> for NanVal {
+    fn from(n: u32) -> Self {
+        Self(uint::from_u32(n))
+    }
+}
+
+/// Wraps the given integer as a uint, via [`uint::from_u32`]; this always succeeds.
+impl From<u16> for NanVal {
+    fn from(n: u16) -> Self {
+        Self(uint::from_u32(n as u32))
+    }
+}
+
+/// Wraps the given integer as a uint, via [`uint::from_u32`]; this always succeeds.
+impl From<u8> for NanVal {
+    fn from(n: u8) -> Self {
+        Self(uint::from_u32(n as u32))
+    }
+}
+
+#[test]
+fn test_nanval_from_small_uint() {
+    assert_eq!(NanVal::from(300u16).as_uint(), Some(300));
+    assert_eq!(NanVal::from(255u8).as_uint(), Some(255));
+    assert_eq!(NanVal::from(u32::MAX).as_uint(), Some(u32::MAX as u64));
+    assert!(NanVal::from(1u32).is_uint() && !NanVal::from(1u32).is_float());
+}
+
+/// Wraps the given float, turning any `NaN` into the [`CANONICAL_NAN_BITS`]; see [`wrap_float`].
+impl From<f64> for NanVal {
+    fn from(value: f64) -> Self {
+        Self(wrap_float(value))
+    }
+}
+
+/// Defaults to the [`NanVal::NULL`] singleton, rather than the float `0.0`;
+/// so that a defaulted value reads as absent, instead of as a number.
+#[cfg(feature = "cell")]
+impl Default for NanVal {
+    fn default() -> Self {
+        Self::NULL
+    }
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_nanval_default() {
+    let value = NanVal::default();
+    assert!(value.is_null());
+    assert!(!value.is_float() && !value.is_uint());
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_nanval_as_ptr_if_tag() {
+    let ptr = 0x1000 as *const ();
+    let value = NanVal::from(nanval!(cell Tag5, ptr));
+    assert_eq!(value.as_ptr_if_tag::<{cell::CELL_TAG_5}>(), Some(ptr));
+    assert_eq!(value.as_ptr_if_tag::<{cell::CELL_TAG_4}>(), None);
+    assert_eq!(NanVal::from(1.5).as_ptr_if_tag::<{cell::CELL_TAG_5}>(), None);
+}
+
+/// Divides two uints; a division by zero or a non-uint operand results in [`NanVal::ERROR`].
+#[cfg(feature = "cell")]
+impl core::ops::Div for NanVal {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        match (self.as_uint(), rhs.as_uint()) {
+            (Some(a), Some(b)) if b != 0 => Self(uint::wrap_unchecked(a / b)),
+            _ => Self::ERROR
+        }
+    }
+}
+
+/// Takes the remainder of two uints; a division by zero or a non-uint operand results in [`NanVal::ERROR`].
+#[cfg(feature = "cell")]
+impl core::ops::Rem for NanVal {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        match (self.as_uint(), rhs.as_uint()) {
+            (Some(a), Some(b)) if b != 0 => Self(uint::wrap_unchecked(a % b)),
+            _ => Self::ERROR
+        }
+    }
+}
+
+/// Complements the data bits of a uint, keeping its marker; any other kind results in [`NanVal::ERROR`].
+#[cfg(feature = "cell")]
+impl core::ops::Not for NanVal {
+    type Output = Self;
+    fn not(self) -> Self {
+        match self.as_uint() {
+            Some(data) => Self(uint::wrap_unchecked(!data & uint::UINT_DATA_BITS)),
+            None => Self::ERROR
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_nanval_div_rem() {
+    let uint = |n| NanVal::from(uint::wrap_unchecked(n));
+    assert_eq!(uint(42) / uint(5), uint(8));
+    assert_eq!(uint(42) % uint(5), uint(2));
+    assert_eq!(uint(42) / uint(0), NanVal::ERROR);
+    assert_eq!(uint(42) % uint(0), NanVal::ERROR);
+    assert_eq!(NanVal::from(1.5) / uint(5), NanVal::ERROR);
+    assert_eq!(uint(42) % NanVal::from(1.5), NanVal::ERROR);
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_nanval_not() {
+    let zero = !NanVal::from(uint::wrap_unchecked(0));
+    assert!(zero.is_uint());
+    assert_eq!(zero.as_uint(), Some(uint::UINT_DATA_BITS));
+    assert_eq!(!zero, NanVal::from(uint::wrap_unchecked(0)));
+    assert_eq!(!NanVal::from(1.5), NanVal::ERROR);
+    assert_eq!(!NanVal::NULL, NanVal::ERROR);
+}
+
+impl core::fmt::Debug for NanVal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("NanVal").field(&self.decode()).finish()
+    }
+}
+
+/// Formats floats via their own `Display`, uints as plain integers, and cells as `#<tag:N data:M>`.
+impl core::fmt::Display for NanVal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.decode() {
+            ValueKind::Float(value) => core::fmt::Display::fmt(&value, f),
+            ValueKind::Uint(data) => core::fmt::Display::fmt(&data, f),
+            #[cfg(feature = "cell")]
+            ValueKind::Cell { tag, data } => write!(f, "#<tag:{} data:{data}>", tag.to_u8()),
+            ValueKind::Unknown(bits) => write!(f, "#<unknown:{bits:#018x}>"),
+        }
+    }
+}
+
+/// Views the given values as `f64`s, without checking that they are floats.
+/// 
+/// # Safety
+/// Every value must be a float (see [`NanVal::is_float`]); otherwise NaN-tagged values are read as `NaN`s,
+/// and writing them back as floats would lose their tag and data.
+#[inline(always)]
+pub unsafe fn as_f64_slice_unchecked(values: &[NanVal]) -> &[f64] {
+    debug_assert!(values.iter().all(NanVal::is_float), "not every value is a float");
+    // SAFETY: `NanVal` is a `repr(transparent)` `u64`, which has the same size and alignment as `f64`.
+    unsafe { core::slice::from_raw_parts(values.as_ptr() as *const f64, values.len()) }
+}
+
+/// Views the given values as `f64`s, if every one of them is a float.
+#[inline(always)]
+pub fn as_f64_slice(values: &[NanVal]) -> Option<&[f64]> {
+    match values.iter().all(NanVal::is_float) {
+        // SAFETY: Every value was just checked to be a float.
+        true => Some(unsafe { as_f64_slice_unchecked(values) }),
+        false => None
+    }
+}
+
+#[test]
+fn test_as_f64_slice() {
+    let floats = [NanVal::from(1.5), NanVal::from(-2.0), NanVal::from(f64::INFINITY)];
+    assert_eq!(as_f64_slice(&floats), Some(&[1.5, -2.0, f64::INFINITY][..]));
+    assert_eq!(as_f64_slice(&[]), Some(&[][..]));
+    
+    let mixed = [NanVal::from(1.5), NanVal::from(uint::wrap_unchecked(42))];
+    assert_eq!(as_f64_slice(&mixed), None);
+    #[cfg(feature = "cell")]
+    {
+        let cell = NanVal::from(cell::from_tag_and_data(cell::CellTag::Tag4, 42).unwrap());
+        assert_eq!(as_f64_slice(&[NanVal::from(1.5), cell]), None);
+    }
+}
+
+#[test]
+fn test_nanval_methods() {
+    let float = NanVal::from(1.5);
+    assert!(float.is_float() && !float.is_nanval() && !float.is_uint());
+    assert_eq!(float.as_float(), Some(1.5));
+    assert_eq!(float.payload(), None);
+    
+    let uint = NanVal::from(uint::wrap_unchecked(42));
+    assert!(uint.is_uint() && uint.is_nanval() && !uint.is_float());
+    assert_eq!(uint.as_uint(), Some(42));
+    assert!(matches!(uint.decode(), ValueKind::Uint(42)));
+    
+    assert!(NanVal::from(f64::NAN).is_float());
+    assert_eq!(NanVal::from(-0.0).canonicalize(), NanVal::from(0.0));
+    assert_ne!(NanVal::from(-0.0), NanVal::from(0.0));
+    assert_eq!(NanVal::from(uint::wrap_unchecked(7)).as_raw_bits_64(), uint::wrap_unchecked(7));
+    
+    let sint = NanVal::from(sint::wrap_sint(-7).unwrap());
+    assert!(sint.is_sint() && !sint.is_uint());
+    assert_eq!(sint.as_sint(), Some(-7));
+    
+    #[cfg(feature = "cell")]
+    {
+        let cell = NanVal::from(cell::from_tag_and_data(cell::CellTag::Tag4, 42).unwrap());
+        assert!(cell.is_cell());
+        assert_eq!(cell.tag(), Some(cell::CellTag::Tag4));
+        assert_eq!(cell.as_cell(), Some(42));
+        
+        assert!(NanVal::NULL.is_null() && !NanVal::NULL.is_undefined());
+        assert!(NanVal::UNDEFINED.is_undefined() && !NanVal::UNDEFINED.is_null());
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_nanval_fmt() {
+    assert_eq!(format!("{}", NanVal::from(1.5)), "1.5");
+    assert_eq!(format!("{}", NanVal::from(uint::wrap_unchecked(42))), "42");
+    assert_eq!(format!("{:?}", NanVal::from(1.5)), "NanVal(Float(1.5))");
+    assert_eq!(format!("{:?}", NanVal::from(uint::wrap_unchecked(42))), "NanVal(Uint(42))");
+    assert_eq!(format!("{:?}", NanVal::from(SIGN_BIT | NAN_BITS | 1)), "NanVal(Unknown(18444492273895866369))");
+    #[cfg(feature = "cell")]
+    {
+        let tagged = NanVal::from(cell::from_tag_and_data(cell::CellTag::Tag3, 42).unwrap());
+        assert_eq!(format!("{tagged:?}"), "NanVal(Cell { tag: Tag3, data: 42 })");
+        let cell = NanVal::from(cell::from_tag_and_data(cell::CellTag::Tag4, 42).unwrap());
+        assert_eq!(format!("{cell}"), "#<tag:4 data:42>");
+        assert_eq!(format!("{}", NanVal::from(cell::CELL_MARKER_BITS | 1)), "#<unknown:0xfff8000000000001>");
+    }
+}
+
+#[test]
+fn test_const_predicates() {
+    const { assert!(is_float_u64(CANONICAL_NAN_BITS)) };
+    const { assert!(is_float_u64(1.5f64.to_bits())) };
+    const { assert!(is_nanval_u64(uint::wrap_unchecked(42))) };
+    const { assert!(uint::is_uint_u64(uint::wrap_unchecked(42))) };
+    #[cfg(feature = "cell")]
+    const { assert!(cell::is_cell_u64(cell::CELL_MARKER_BITS | cell::CELL_TAG_1)) };
+}
+
+#[test]
+fn test_nanval_macro() {
+    assert_eq!(nanval!(1.5f64), 1.5f64.to_bits());
+    assert_eq!(nanval!(uint 42), uint::wrap_unchecked(42));
+    #[cfg(feature = "cell")] {
+        let ptr = 0x1000 as *const ();
+        assert_eq!(nanval!(cell Tag4, ptr), cell::from_tag_and_data(cell::CellTag::Tag4, 0x1000).unwrap());
+    }
+}
+
+#[test]
+#[cfg(not(feature = "quiet-nan-is-zero"))]
+fn test_quiet_nan_is_one() {
+    assert_eq!(wrap_float(f64::NAN), NAN_BITS);
+    assert!(is_float(NAN_BITS));
+    assert!(is_float((NAN_BITS & !QUIET_NAN_BIT) | 1));
+    assert!(matches!(decode(wrap_float(f64::NAN)), ValueKind::Float(_)));
+}
+
+#[test]
+#[cfg(feature = "quiet-nan-is-zero")]
+fn test_quiet_nan_is_zero() {
+    assert_eq!(wrap_float(f64::NAN), 0x7FF7FFFFFFFFFFFF);
+    assert!(!is_float(NAN_BITS));
+    assert_eq!(uint::unwrap_uint(NAN_BITS), Some(0));
+    assert!(is_float(CANONICAL_NAN_BITS) && unwrap_float_unchecked(CANONICAL_NAN_BITS).is_nan());
+    assert!(matches!(decode(wrap_float(f64::NAN)), ValueKind::Float(_)));
+}
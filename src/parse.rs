@@ -0,0 +1,139 @@
+//! Parsing of literals into NaN-tagged values, for REPLs and the like.
+//! 
+//! The following literals are accepted, optionally surrounded by ASCII whitespace:
+//! - Hexadecimal integers, such as `0xFF`, which become a [`crate::uint`].
+//! - Decimal numbers with an optional fraction and exponent, such as `42`, `-0.0` or `1e10`, which become an `f64`.
+//! - The special floats `inf`, `infinity` and `nan` (ignoring case), with an optional sign.
+//! - The singletons `null` and `undefined`, which become [`crate::cell::NULL`] and [`crate::cell::UNDEFINED`]; only with the `cell` feature.
+
+use super::{uint, wrap_float, NanError};
+#[cfg(test)]
+use super::cons::*;
+
+/// Parses the given literal into a NaN-tagged value.
+/// 
+/// Any `NaN` is returned as [`CANONICAL_NAN_BITS`](crate::cons::CANONICAL_NAN_BITS), so that it doesn't end up being mistaken for a NaN-tagged value.
+/// 
+/// If the literal is malformed, [`NanError::InvalidSyntax`] is returned with the byte-position of the offending character.
+pub fn parse_value(s: &str) -> Result<u64, NanError> {
+    let bytes = s.as_bytes();
+    let start = bytes.iter().position(|b| !b.is_ascii_whitespace()).unwrap_or(bytes.len());
+    let end = bytes.iter().rposition(|b| !b.is_ascii_whitespace()).map_or(start, |i| i + 1);
+    let text = &s[start..end];
+    
+    if text.is_empty() {
+        return Err(NanError::InvalidSyntax { position: start })
+    }
+    
+    #[cfg(feature = "cell")]
+    match text {
+        "null" => return Ok(crate::cell::NULL),
+        "undefined" => return Ok(crate::cell::UNDEFINED),
+        _ => {}
+    }
+    
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(digits) => parse_hex(digits, start + 2),
+        None => parse_float(text, start)
+    }
+}
+
+fn parse_hex(digits: &str, offset: usize) -> Result<u64, NanError> {
+    if digits.is_empty() {
+        return Err(NanError::InvalidSyntax { position: offset })
+    }
+    
+    let mut value: u64 = 0;
+    for (i, c) in digits.bytes().enumerate() {
+        let digit = (c as char).to_digit(16).ok_or(NanError::InvalidSyntax { position: offset + i })?;
+        value = value.checked_mul(16)
+            .and_then(|v| v.checked_add(digit as u64))
+            .ok_or(NanError::OutOfRange)?;
+    }
+    
+    uint::box_u64(value)
+}
+
+fn parse_float(text: &str, offset: usize) -> Result<u64, NanError> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        i += 1;
+    }
+    
+    let word = &text[i..];
+    let special = ["inf", "infinity", "nan"].iter().any(|s| word.eq_ignore_ascii_case(s));
+    if !special {
+        let mut digits = skip_digits(bytes, &mut i);
+        if bytes.get(i) == Some(&b'.') {
+            i += 1;
+            digits += skip_digits(bytes, &mut i);
+        }
+        if digits == 0 {
+            return Err(NanError::InvalidSyntax { position: offset + i })
+        }
+        
+        if matches!(bytes.get(i), Some(b'e' | b'E')) {
+            i += 1;
+            if matches!(bytes.get(i), Some(b'+' | b'-')) {
+                i += 1;
+            }
+            if skip_digits(bytes, &mut i) == 0 {
+                return Err(NanError::InvalidSyntax { position: offset + i })
+            }
+        }
+        
+        if i != bytes.len() {
+            return Err(NanError::InvalidSyntax { position: offset + i })
+        }
+    }
+    
+    // The grammar was checked above, so this only fails on inputs `core` rejects for other reasons.
+    let value: f64 = text.parse().map_err(|_| NanError::InvalidSyntax { position: offset })?;
+    Ok(wrap_float(value))
+}
+
+/// Advances `i` past any ASCII digits, returning how many there were.
+fn skip_digits(bytes: &[u8], i: &mut usize) -> usize {
+    let start = *i;
+    while bytes.get(*i).is_some_and(u8::is_ascii_digit) {
+        *i += 1;
+    }
+    *i - start
+}
+
+#[test]
+fn test_parse_value() {
+    assert_eq!(parse_value("0xFF"), Ok(uint::UINT_MARKER_BITS | 0xFF));
+    assert_eq!(parse_value("0x0"), Ok(uint::UINT_MARKER_BITS));
+    assert_eq!(parse_value("1e10"), Ok(1e10f64.to_bits()));
+    assert_eq!(parse_value("-2.5E-3"), Ok((-2.5e-3f64).to_bits()));
+    assert_eq!(parse_value("-0.0"), Ok(NEG_ZERO_BITS));
+    assert_eq!(parse_value(" 42 "), Ok(42f64.to_bits()));
+    assert_eq!(parse_value(".5"), Ok(0.5f64.to_bits()));
+    assert_eq!(parse_value("-inf"), Ok(NEG_INF_BITS));
+    assert_eq!(parse_value("NaN"), Ok(CANONICAL_NAN_BITS));
+    assert_eq!(parse_value("-nan"), Ok(CANONICAL_NAN_BITS));
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_parse_value_singletons() {
+    assert_eq!(parse_value("null"), Ok(crate::cell::NULL));
+    assert_eq!(parse_value(" undefined "), Ok(crate::cell::UNDEFINED));
+    assert_eq!(parse_value("NULL"), Err(NanError::InvalidSyntax { position: 0 }));
+    assert_eq!(parse_value("nullx"), Err(NanError::InvalidSyntax { position: 0 }));
+}
+
+#[test]
+fn test_parse_value_errors() {
+    assert_eq!(parse_value(""), Err(NanError::InvalidSyntax { position: 0 }));
+    assert_eq!(parse_value("1.2.3"), Err(NanError::InvalidSyntax { position: 3 }));
+    assert_eq!(parse_value("  12x"), Err(NanError::InvalidSyntax { position: 4 }));
+    assert_eq!(parse_value("1e"), Err(NanError::InvalidSyntax { position: 2 }));
+    assert_eq!(parse_value("-"), Err(NanError::InvalidSyntax { position: 1 }));
+    assert_eq!(parse_value("0x"), Err(NanError::InvalidSyntax { position: 2 }));
+    assert_eq!(parse_value("0xFG"), Err(NanError::InvalidSyntax { position: 3 }));
+    assert_eq!(parse_value("0xFFFFFFFFFFFFFFFFF"), Err(NanError::OutOfRange));
+    assert_eq!(parse_value("0x8000000000000"), Err(NanError::OutOfRange));
+}
@@ -5,15 +5,22 @@ use super::cons::*;
 /// 
 /// Since the trait is only `impl`d as the input of functions,
 /// monomorphization will cause a N×M relation of structs implementing this trait,
-/// with the various functions consuming this trait; at the time of writing this,
-/// that would be `5×19`, yielding `95` functions for rustc to emit... which is acceptable.
+/// with the various functions consuming this trait; which is acceptable,
+/// as those functions are tiny and `#[inline(always)]`.
 /// 
 /// **Note:** It is *highly* recommended to refer to this traits methods in static-form; ie: `IntoRawBits64::XXX`.
+/// 
+/// **Note:** The small integer types are *raw* bit-patterns, zero- or sign-extended to 64 bits,
+/// not pre-tagged values; ie: `uint::is_uint(42_u32)` is `false`, use [`crate::uint::wrap`] to create a uint.
 pub trait IntoRawBits64: Copy + Clone + Sized {
     /// Returns the raw bits that `self` contains.
     fn as_raw_bits_64(&self) -> u64;
     
     /// Returns the raw bits that `self` contains, but without the sign-bit.
+    /// 
+    /// **Note:** The sign-bit is what distinguishes a cell from a uint,
+    /// so a cell loses its marker and becomes a uint-looking value;
+    /// do *not* use this before checking for a cell, use [`crate::is_nanval`] to check for any NaN-tagged value.
     #[inline(always)]
     fn as_raw_bits_64_without_sign(&self) -> u64 {
         self.as_raw_bits_64() & !SIGN_BIT
@@ -31,10 +38,10 @@ pub trait IntoRawBits64: Copy + Clone + Sized {
         crate::is_nanval(*self)
     }
     
-    /// Checks if the value is a floating point number representing infinity.
+    /// Checks if the value is the canonical floating point `NaN`; see [`CANONICAL_NAN_BITS`].
     #[inline(always)]
     fn is_nan(&self) -> bool {
-        self.as_raw_bits_64() == NAN_BITS
+        self.as_raw_bits_64() == CANONICAL_NAN_BITS
     }
     
     /// Checks if the value is a floating point number representing infinity.
@@ -94,6 +101,62 @@ impl IntoRawBits64 for core::primitive::u64 {
     }
 }
 
+impl IntoRawBits64 for core::primitive::i64 {
+    /// Reinterprets the bits, instead of converting the value.
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl IntoRawBits64 for core::primitive::u32 {
+    /// Zero-extends the value to 64 bits.
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl IntoRawBits64 for core::primitive::i32 {
+    /// Sign-extends the value to 64 bits, then reinterprets the bits.
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as i64 as u64
+    }
+}
+
+impl IntoRawBits64 for core::primitive::u16 {
+    /// Zero-extends the value to 64 bits.
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl IntoRawBits64 for core::primitive::i16 {
+    /// Sign-extends the value to 64 bits, then reinterprets the bits.
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as i64 as u64
+    }
+}
+
+impl IntoRawBits64 for core::primitive::u8 {
+    /// Zero-extends the value to 64 bits.
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl IntoRawBits64 for core::primitive::i8 {
+    /// Sign-extends the value to 64 bits, then reinterprets the bits.
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as i64 as u64
+    }
+}
+
 impl IntoRawBits64 for core::num::NonZeroU64 {
     #[inline(always)]
     fn as_raw_bits_64(&self) -> u64 {
@@ -101,15 +164,95 @@ impl IntoRawBits64 for core::num::NonZeroU64 {
     }
 }
 
+impl IntoRawBits64 for *const () {
+    /// Returns the address of the pointer.
+    /// 
+    /// The resulting bit-pattern is platform-specific; on systems with a true 64-bit virtual address space,
+    /// the address may not fit into 48 bits, as required by [`crate::cell::from_tag_and_pointer`].
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as u64
+    }
+}
+
+impl IntoRawBits64 for *mut () {
+    /// Returns the address of the pointer.
+    /// 
+    /// The resulting bit-pattern is platform-specific; on systems with a true 64-bit virtual address space,
+    /// the address may not fit into 48 bits, as required by [`crate::cell::from_tag_and_pointer`].
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        *self as u64
+    }
+}
+
 impl IntoRawBits64 for &[core::primitive::u8] {
     #[inline(always)]
     fn as_raw_bits_64(&self) -> u64 {
         match self.len() >= 8 {
             true => {
-                let bytes = self[0..=3].try_into().unwrap();
+                let bytes = self[0..8].try_into().unwrap();
                 core::primitive::u64::from_ne_bytes(bytes)
             },
             false => panic!("not enough bytes to transmute into a u64")
         }
     }
 }
+
+#[test]
+fn test_byte_slice_bits() {
+    let bytes: &[u8] = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+    assert_eq!((&bytes[..8]).as_raw_bits_64(), u64::from_ne_bytes([1, 2, 3, 4, 5, 6, 7, 8]));
+    assert_eq!(bytes.as_raw_bits_64(), u64::from_ne_bytes([1, 2, 3, 4, 5, 6, 7, 8]));
+}
+
+#[test]
+#[should_panic(expected = "not enough bytes")]
+fn test_byte_slice_too_short() {
+    let bytes: &[u8] = &[1, 2, 3, 4, 5, 6, 7];
+    bytes.as_raw_bits_64();
+}
+
+#[test]
+fn test_i64_bits() {
+    assert_eq!((-1_i64).as_raw_bits_64(), u64::MAX);
+    assert_eq!(i64::MIN.as_raw_bits_64(), SIGN_BIT);
+    assert_eq!(42_i64.as_raw_bits_64(), 42);
+    assert!(crate::is_nanval(NEG_INF_BITS as i64 | NAN_BITS as i64));
+}
+
+#[test]
+fn test_small_int_bits() {
+    assert_eq!(42_u32.as_raw_bits_64(), 42);
+    assert_eq!(u32::MAX.as_raw_bits_64(), 0xFFFFFFFF);
+    assert_eq!(u16::MAX.as_raw_bits_64(), 0xFFFF);
+    assert_eq!(u8::MAX.as_raw_bits_64(), 0xFF);
+    assert_eq!((-1_i32).as_raw_bits_64(), u64::MAX);
+    assert_eq!((-1_i16).as_raw_bits_64(), u64::MAX);
+    assert_eq!((-1_i8).as_raw_bits_64(), u64::MAX);
+    assert_eq!(i8::MIN.as_raw_bits_64(), i8::MIN as i64 as u64);
+    assert!(!crate::uint::is_uint(42_u32));
+    assert!(crate::is_float(42_u32));
+}
+
+#[test]
+fn test_pointer_bits() {
+    let mut value = 42u64;
+    let ptr = &value as *const u64 as *const ();
+    assert_eq!(ptr.as_raw_bits_64(), ptr as usize as u64);
+    assert!(crate::is_float(ptr));
+    
+    let ptr = &mut value as *mut u64 as *mut ();
+    assert_eq!(ptr.as_raw_bits_64(), ptr as usize as u64);
+    assert_eq!(core::ptr::null::<()>().as_raw_bits_64(), 0);
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_without_sign_clears_cell_marker() {
+    let cell = crate::cell::from_tag_and_data(crate::cell::CellTag::Tag4, 42).unwrap();
+    assert!(crate::cell::is_cell(cell));
+    assert!(!crate::cell::is_cell(cell.as_raw_bits_64_without_sign()));
+    assert!(crate::uint::is_uint(cell.as_raw_bits_64_without_sign()));
+    assert!(crate::is_nanval(cell.as_raw_bits_64_without_sign()));
+}
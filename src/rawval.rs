@@ -0,0 +1,486 @@
+//! A typed NaN-tagged value, [`RawNaNVal`], holding either a `f64` or a 16-bit tag with 32 bits of data.
+//! 
+//! Bit Layout of a tagged value is as follows:
+//! ```text
+//! 0111 1111 1111 1000 tttt tttt tttt tttt xxxx xxxx xxxx xxxx xxxx xxxx xxxx xxxx
+//! ```
+//! 
+//! - Tag  `t`, 16 bits; must be non-zero.
+//! - Data `x`, 32 bits.
+
+use super::*;
+use core::num::NonZeroU16;
+
+const TAG_SHIFT: u64 = 32;
+const TAG_MASK:   u64 = 0x0000FFFF00000000;
+//const TAG_UNMASK: u64 = 0xFFFF0000FFFFFFFF;
+
+const DAT_MASK:   u64 = 0x00000000FFFFFFFF;
+const DAT_UNMASK: u64 = 0xFFFFFFFF00000000;
+
+/// A tag that can be converted from/into a `NonZeroU16`.
+pub trait NaNTag: From<NonZeroU16> + Into<NonZeroU16> + Clone + Copy {}
+impl NaNTag for NonZeroU16 {}
+
+/// Data that can be converted from/into a `u32`.
+pub trait NaNDat: From<u32> + Into<u32> + Clone + Copy {}
+impl NaNDat for u32 {}
+
+/// A raw NaN-tagged value.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub union RawNaNVal<TAG, DAT> where TAG: NaNTag, DAT: NaNDat {
+    f: f64,
+    u: u64,
+    p: core::marker::PhantomData<(TAG,DAT)>
+}
+
+impl<TAG: NaNTag, DAT: NaNDat> RawNaNVal<TAG, DAT> {
+    /// Creates a new [`RawNaNVal`] from the given `f64`, if valid.
+    pub fn from_float(f: f64) -> Option<Self> {
+        let new = Self {f};
+        if new.has_tag() {
+            return None
+        }
+        Some(new)
+    }
+    
+    /// Creates a new [`RawNaNVal`] from the given `f64`.
+    /// 
+    /// # Safety
+    /// If there is a tag set, the returned [`RawNaNVal`] will have an invalid tag and data.
+    pub fn from_float_unchecked(f: f64) -> Self {
+        let new = Self {f};
+        debug_assert!(!new.has_tag(), "a float must not have tag");
+        new
+    }
+    
+    /// Creates a new [`RawNaNVal`] from the given raw bits, as-is.
+    /// 
+    /// **Note:** Adversarially chosen bits, such as a tag of `0`, may produce a value
+    /// that neither is a valid float nor has a valid tag, even though `has_f64` or `has_tag` claim so.
+    pub fn from_raw_u64(bits: u64) -> Self {
+        Self {u: bits}
+    }
+    
+    /// Returns the raw bits of `self`.
+    pub fn to_bits(self) -> u64 {
+        // Every bit-pattern is a valid `u64`, and the union is always fully initialized, so this is safe.
+        unsafe {self.u}
+    }
+    
+    /// Creates a new [`RawNaNVal`] from the given tag and no data.
+    pub fn from_tag(tag: TAG) -> Self {
+        let tag: u16 = tag.into().get();
+        let tag = (tag as u64) << TAG_SHIFT;
+        let tag = tag & TAG_MASK; // no spilling
+        Self::from_raw_u64(NAN_SIGNAL | tag)
+    }
+    
+    /// Creates a new [`RawNaNVal`] from the given tag and data.
+    pub fn from_tag_and_data(tag: TAG, dat: DAT) -> Self {
+        let tag: u16 = tag.into().get();
+        let tag = (tag as u64) << TAG_SHIFT;
+        let tag = tag & TAG_MASK; // no spilling
+        
+        let dat: u32 = dat.into();
+        let dat = dat as u64;
+        let dat = dat & DAT_MASK; // no spilling
+        
+        Self::from_raw_u64(NAN_SIGNAL | tag | dat)
+    }
+    
+    /// Returns the tag, ignoring the signal
+    /// 
+    /// # Safety
+    /// If the tag is not set (use `has_tag` to check), undefined behaviour will occur.
+    pub unsafe fn get_tag_unchecked(&self) -> TAG {
+        debug_assert!(self.has_tag(), "invalid tag");
+        let tag = (self.u & TAG_MASK) >> TAG_SHIFT;
+        NonZeroU16::new_unchecked(tag as u16).into()
+    }
+    
+    /// Returns the data, ignoring the signal
+    /// 
+    /// # Safety
+    /// You can (and will) receive corrupted data if the `has_tag`-check is skipped.
+    pub fn get_dat_raw_unchecked(&self) -> DAT {
+        debug_assert!(self.has_tag(), "invalid tag");
+        unsafe {((self.u & DAT_MASK) as u32).into()}
+    }
+    
+    /// Returns if `self` is a tag & data.
+    /// 
+    /// The tag must be non-zero, as it is a `NonZeroU16`; this also keeps the plain `NaN` a float.
+    pub fn has_tag(&self) -> bool {
+        let bits = self.to_bits();
+        (bits & NAN_MASK) == NAN_SIGNAL && (bits & TAG_MASK) != 0
+    }
+    
+    /// Returns if `self` is a `f64`.
+    pub fn has_f64(&self) -> bool {
+        !self.has_tag()
+    }
+    
+    /// Overrides `self`s tag with the given tag.
+    /// 
+    /// # Safety
+    /// You can (and will) receive corrupted data if the `has_tag`-check is skipped.
+    pub fn set_tag_unchecked(&mut self, tag: TAG) {
+        debug_assert!(self.has_tag(), "retagging a float is invalid");
+        let tag: u16 = tag.into().get();
+        let tag = (tag as u64) << TAG_SHIFT;
+        let tag = tag & TAG_MASK; // no spilling
+        unsafe {
+            self.u &= NAN_UNMASK & !TAG_MASK; // remove NaN and tag
+            self.u |= NAN_SIGNAL | tag; // insert NaN and tag
+        }
+    }
+    
+    /// Overrides `self`s data with the given data.
+    /// 
+    /// # Safety
+    /// You can (and will) receive corrupted data if the `has_tag`-check is skipped.
+    pub fn set_dat_unchecked(&mut self, dat: DAT) {
+        debug_assert!(self.has_tag(), "partially rewriting a float is invalid");
+        let dat: u32 = dat.into();
+        let dat = dat as u64;
+        let dat = dat & DAT_MASK; // no spilling
+        unsafe {
+            self.u &= DAT_UNMASK; // remove dat
+            self.u |= dat; // insert dat
+        }
+    }
+    
+    /// Overrides `self`s tag and data with the given values.
+    pub fn set_tag_and_dat(&mut self, tag: TAG, dat: DAT) {
+        let tag: u16 = tag.into().get();
+        let tag = (tag as u64) << TAG_SHIFT;
+        let tag = tag & TAG_MASK; // no spilling
+        
+        let dat: u32 = dat.into();
+        let dat = dat as u64;
+        let dat = dat & DAT_MASK; // no spilling
+        
+        // This is locally safe, as the data is fully overwritten.
+        self.u = NAN_SIGNAL | tag | dat;
+    }
+    
+    /// Tries to create a copy of `self`, with the given `DAT`, using the existing `TAG`.
+    pub fn with_dat(&self, dat: DAT) -> Option<Self> {
+        if self.has_tag() {
+            let mut n = *self;
+            n.set_dat_unchecked(dat);
+            return Some(n)
+        }
+        
+        None
+    }
+    
+    /// Tries to create a copy of `self`, with its `DAT` transformed by `f`, using the existing `TAG`.
+    pub fn map_dat<F>(&self, f: F) -> Option<Self> where F: FnOnce(DAT) -> DAT {
+        if self.has_tag() {
+            let mut n = *self;
+            n.set_dat_unchecked(f(self.get_dat_raw_unchecked()));
+            return Some(n)
+        }
+        
+        None
+    }
+    
+    /// Tries to create a copy of `self`, with its `TAG` transformed by `f`, using the existing `DAT`.
+    pub fn map_tag<F>(&self, f: F) -> Option<Self> where F: FnOnce(TAG) -> TAG {
+        if self.has_tag() {
+            let mut n = *self;
+            // We just checked that there is a tag, so this is safe.
+            n.set_tag_unchecked(f(unsafe {self.get_tag_unchecked()}));
+            return Some(n)
+        }
+        
+        None
+    }
+    
+    /// Replaces `self` with the given `f64`-value.
+    pub fn set_f64(&mut self, f: f64) {
+        // This is locally safe, as the data is fully overwritten.
+        self.f = f;
+    }
+    
+    /// Returns the contained `f64`-value, or `None`.
+    pub fn get_f64(&self) -> Option<f64> {
+        if self.has_tag() {
+            None
+        } else {
+            // We just checked that there is no tag, so this is safe.
+            Some(unsafe {self.f})
+        }
+    }
+    
+    /// Returns the tag, or `None`.
+    pub fn get_tag(&self) -> Option<TAG> {
+        if self.has_tag() {
+            // We just checked that there is a tag, so this is safe.
+            let tag = unsafe {self.get_tag_unchecked()};
+            Some(tag)
+        } else {
+            None
+        }
+    }
+    
+    /// Returns the data, or `None`.
+    pub fn get_dat(&self) -> Option<DAT> {
+        if self.has_tag() {
+            // We just checked that there is a tag, so this is safe.
+            Some(self.get_dat_raw_unchecked())
+        } else {
+            None
+        }
+    }
+    
+    /// Returns the tag and data, or `None`.
+    pub fn get_tag_and_dat(&self) -> Option<(TAG, DAT)> {
+        if self.has_tag() {
+            // We just checked that there is a tag, so this is safe.
+            Some((
+                unsafe{self.get_tag_unchecked()},
+                self.get_dat_raw_unchecked()
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+impl<TAG: NaNTag + core::fmt::Debug, DAT: NaNDat + core::fmt::Debug> core::fmt::Debug for RawNaNVal<TAG, DAT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.get_f64(), self.get_tag_and_dat()) {
+            (Some(value), _) => f.debug_tuple("Float").field(&value).finish(),
+            (None, Some((tag, dat))) => f.debug_struct("Tagged").field("tag", &tag).field("data", &dat).finish(),
+            // `get_f64` and `get_tag_and_dat` are mutually exclusive.
+            (None, None) => unreachable!()
+        }
+    }
+}
+
+/// Formats floats via their own `Display`, and tagged values as `#<tag:N data:M>`.
+impl<TAG: NaNTag + core::fmt::Display, DAT: NaNDat + core::fmt::Display> core::fmt::Display for RawNaNVal<TAG, DAT> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match (self.get_f64(), self.get_tag_and_dat()) {
+            (Some(value), _) => core::fmt::Display::fmt(&value, f),
+            (None, Some((tag, dat))) => write!(f, "#<tag:{tag} data:{dat}>"),
+            // `get_f64` and `get_tag_and_dat` are mutually exclusive.
+            (None, None) => unreachable!()
+        }
+    }
+}
+
+/// Two values are equal if and only if their raw bits are identical.
+impl<TAG: NaNTag, DAT: NaNDat> PartialEq for RawNaNVal<TAG, DAT> {
+    fn eq(&self, other: &Self) -> bool {
+        // Every bit-pattern is a valid `u64`, so this is safe.
+        unsafe {self.u == other.u}
+    }
+}
+
+impl<TAG: NaNTag, DAT: NaNDat> Eq for RawNaNVal<TAG, DAT> {}
+
+/// Hashes the raw bits, in agreement with the [`PartialEq`] impl.
+impl<TAG: NaNTag, DAT: NaNDat> core::hash::Hash for RawNaNVal<TAG, DAT> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // Every bit-pattern is a valid `u64`, so this is safe.
+        unsafe {self.u}.hash(state)
+    }
+}
+
+impl<TAG: NaNTag, DAT: NaNDat> IntoRawBits64 for RawNaNVal<TAG, DAT> {
+    #[inline(always)]
+    fn as_raw_bits_64(&self) -> u64 {
+        self.to_bits()
+    }
+}
+
+// The following impl's are always safe.
+
+impl<TAG: NaNTag, DAT: NaNDat> TryFrom<RawNaNVal<TAG, DAT>> for f64 {
+    type Error = ();
+
+    fn try_from(value: RawNaNVal<TAG, DAT>) -> Result<Self, Self::Error> {
+        value.get_f64().ok_or(())
+    }
+}
+
+impl<TAG: NaNTag, DAT: NaNDat> TryFrom<f64> for RawNaNVal<TAG, DAT> {
+    type Error = ();
+    
+    fn try_from(f: f64) -> Result<Self, Self::Error> {
+        let new = Self {f};
+        (!new.has_tag()).then_some(new).ok_or(())
+    }
+}
+
+impl<TAG: NaNTag, DAT: NaNDat> From<TAG> for RawNaNVal<TAG, DAT> {
+    fn from(tag: TAG) -> Self {
+        Self::from_tag(tag)
+    }
+}
+
+impl<TAG: NaNTag, DAT: NaNDat> From<(TAG, DAT)> for RawNaNVal<TAG, DAT> {
+    fn from((tag, dat): (TAG, DAT)) -> Self {
+        Self::from_tag_and_data(tag, dat)
+    }
+}
+
+/// Defaults to the float `0.0`, whose bits are all zero.
+impl<TAG: NaNTag, DAT: NaNDat> Default for RawNaNVal<TAG, DAT> {
+    fn default() -> Self {
+        Self::from_float_unchecked(0.0)
+    }
+}
+
+// SAFETY: The union is `repr(C)` with only 8-byte fields (and a zero-sized marker), so it has no padding,
+// and every bit-pattern is a valid `u64`, and thus a valid value; which includes the zero bit-pattern, the float `0.0`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for RawNaNVal<NonZeroU16, u32> {}
+
+// SAFETY: See the `Zeroable` impl above; the union is also `Copy` and `'static`.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for RawNaNVal<NonZeroU16, u32> {}
+
+#[test]
+fn test_rawval_default() {
+    let value = RawNaNVal::<NonZeroU16, u32>::default();
+    assert_eq!(value.get_f64(), Some(0.0));
+    assert_eq!(value.to_bits(), 0);
+}
+
+#[test]
+fn test_rawval_size() {
+    assert!(core::mem::size_of::<RawNaNVal<NonZeroU16, u32>>() == 8)
+}
+
+#[test]
+fn test_rawval_try_from_f64() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    assert!(Val::try_from(1.5_f64).is_ok_and(|v| v.get_f64() == Some(1.5)));
+    
+    let tagged = Val::from_tag_and_data(NonZeroU16::new(3).unwrap(), 42);
+    let tagged = f64::from_bits(unsafe {tagged.u});
+    assert!(Val::try_from(tagged).is_err());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_rawval_debug() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    assert_eq!(format!("{:?}", Val::from_float(1.5).unwrap()), "Float(1.5)");
+    let tagged = Val::from_tag_and_data(NonZeroU16::new(3).unwrap(), 42);
+    assert_eq!(format!("{tagged:?}"), "Tagged { tag: 3, data: 42 }");
+}
+
+#[test]
+fn test_rawval_eq() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    let tag = |t| NonZeroU16::new(t).unwrap();
+    assert!(Val::from_float(1.5).unwrap() == Val::from_float(1.5).unwrap());
+    assert!(Val::from_float(1.5).unwrap() != Val::from_float(2.5).unwrap());
+    assert!(Val::from_tag_and_data(tag(3), 42) == Val::from_tag_and_data(tag(3), 42));
+    assert!(Val::from_tag_and_data(tag(3), 42) != Val::from_tag_and_data(tag(4), 42));
+    assert!(Val::from_tag_and_data(tag(3), 42) != Val::from_tag_and_data(tag(3), 43));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_rawval_hash() {
+    use std::hash::BuildHasher;
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    let hasher = std::collections::hash_map::RandomState::new();
+    let tagged = Val::from_tag_and_data(NonZeroU16::new(3).unwrap(), 42);
+    assert_eq!(hasher.hash_one(tagged), hasher.hash_one(Val::from_tag_and_data(NonZeroU16::new(3).unwrap(), 42)));
+    
+    let mut set = std::collections::HashSet::new();
+    set.insert(tagged);
+    set.insert(tagged);
+    set.insert(Val::from_float(1.5).unwrap());
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_rawval_display() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    assert_eq!(format!("{}", Val::from_float(1.25).unwrap()), "1.25");
+    assert_eq!(format!("{:.1}", Val::from_float(1.25).unwrap()), "1.2");
+    let tagged = Val::from_tag_and_data(NonZeroU16::new(3).unwrap(), 42);
+    assert_eq!(format!("{tagged}"), "#<tag:3 data:42>");
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn test_rawval_bytemuck_cast() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    let vals = [Val::from_float(1.5).unwrap(), Val::from_tag_and_data(NonZeroU16::new(3).unwrap(), 42)];
+    let bits: &[u64] = bytemuck::cast_slice(&vals);
+    assert_eq!(bits[0], 1.5f64.to_bits());
+    assert_eq!(bytemuck::cast_slice::<u64, Val>(bits), &vals);
+    assert!(<Val as bytemuck::Zeroable>::zeroed().get_f64() == Some(0.0));
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_rawval_into_raw_bits() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    let bits = cell::from_tag_and_data(cell::CellTag::Tag5, 42).unwrap();
+    let val = Val::from_raw_u64(bits);
+    assert_eq!(val.as_raw_bits_64(), bits);
+    assert!(cell::is_cell(val));
+    assert_eq!(cell::extract_tag_and_data(val), Some((cell::CellTag::Tag5, 42)));
+    assert!(is_float(Val::from_float(1.5).unwrap()));
+}
+
+#[test]
+fn test_rawval_raw_bits_roundtrip() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    for bits in [0, 1.5f64.to_bits(), NAN_SIGNAL | 0x0000000300000042, u64::MAX] {
+        assert_eq!(Val::from_raw_u64(bits).to_bits(), bits);
+    }
+    let tagged = Val::from_tag_and_data(NonZeroU16::new(3).unwrap(), 42);
+    assert!(Val::from_raw_u64(tagged.to_bits()) == tagged);
+}
+
+#[test]
+fn test_rawval_map_dat_and_tag() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    let tag = |t| NonZeroU16::new(t).unwrap();
+    let tagged = Val::from_tag_and_data(tag(3), 42);
+    
+    let mapped = tagged.map_dat(|d| d + 1).unwrap();
+    assert!(mapped.get_tag_and_dat() == Some((tag(3), 43)));
+    let mapped = tagged.map_tag(|t| t.saturating_add(1)).unwrap();
+    assert!(mapped.get_tag_and_dat() == Some((tag(4), 42)));
+    
+    let float = Val::from_float(1.5).unwrap();
+    assert!(float.map_dat(|d| d + 1).is_none());
+    assert!(float.map_tag(|t| t).is_none());
+}
+
+#[test]
+fn test_rawval_tag_and_data_do_not_overlap() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    let tag = NonZeroU16::new(u16::MAX).unwrap();
+    let val = Val::from_tag_and_data(tag, u32::MAX);
+    assert!(val.get_tag_and_dat() == Some((tag, u32::MAX)));
+    assert_eq!(val.to_bits(), NAN_SIGNAL | TAG_MASK | DAT_MASK);
+    
+    let val = Val::from_tag_and_data(NonZeroU16::new(1).unwrap(), 0);
+    assert!(val.get_tag_and_dat() == Some((NonZeroU16::new(1).unwrap(), 0)));
+}
+
+#[test]
+fn test_rawval_nan_is_not_tagged() {
+    type Val = RawNaNVal<NonZeroU16, u32>;
+    let nan = Val::from_float(f64::NAN).unwrap();
+    assert!(nan.has_f64() && !nan.has_tag());
+    assert!(nan.get_tag().is_none());
+    // A zero tag is not a valid `NonZeroU16`, so data without a tag is not tagged either.
+    assert!(!Val::from_raw_u64(NAN_SIGNAL | 42).has_tag());
+}
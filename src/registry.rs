@@ -0,0 +1,71 @@
+//! Compile-time registration of the 16-bit tags of a [`crate::rawval::RawNaNVal`].
+//! 
+//! Tags are declared via [`registry_define!`](crate::registry_define), which rejects duplicate tags at compile time;
+//! declaring all tags of a program in a single invocation keeps subsystems from claiming the same tag.
+
+/// Returns wether all of the given tags are distinct; used by [`registry_define!`](crate::registry_define).
+pub const fn all_distinct(tags: &[u16]) -> bool {
+    let mut i = 0;
+    while i < tags.len() {
+        let mut j = i + 1;
+        while j < tags.len() {
+            if tags[i] == tags[j] {return false}
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Declares a set of distinct, named, tags as `NonZeroU16` constants.
+/// 
+/// ```
+/// nanval::registry_define! {
+///     pub STRING_TAG = 1_u16;
+///     pub TABLE_TAG = 2_u16;
+/// }
+/// assert_eq!(TABLE_TAG.get(), 2);
+/// ```
+/// 
+/// A tag that is zero, or that is declared twice, is a compile-time error:
+/// 
+/// ```compile_fail
+/// nanval::registry_define! {
+///     pub STRING_TAG = 1_u16;
+///     pub TABLE_TAG = 1_u16;
+/// }
+/// ```
+#[macro_export]
+macro_rules! registry_define {
+    ($($vis:vis $name:ident = $tag:expr;)*) => {
+        $(
+            $vis const $name: ::core::num::NonZeroU16 = match ::core::num::NonZeroU16::new($tag) {
+                Some(tag) => tag,
+                None => panic!(concat!("the tag `", stringify!($name), "` must not be zero"))
+            };
+        )*
+        const _: () = assert!(
+            $crate::registry::all_distinct(&[$($name.get()),*]),
+            "two tags of the registry share the same value"
+        );
+    };
+}
+
+#[test]
+fn test_all_distinct() {
+    assert!(all_distinct(&[]));
+    assert!(all_distinct(&[1, 2, 3]));
+    assert!(!all_distinct(&[1, 2, 1]));
+}
+
+#[test]
+fn test_registry_define() {
+    use crate::rawval::RawNaNVal;
+    registry_define! {
+        TAG_A = 1_u16;
+        TAG_B = 0x7FFF_u16;
+    }
+    let value: RawNaNVal<_, u32> = RawNaNVal::from_tag_and_data(TAG_B, 42);
+    assert_eq!(value.get_tag_and_dat(), Some((TAG_B, 42)));
+    assert_ne!(TAG_A, TAG_B);
+}
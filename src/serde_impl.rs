@@ -0,0 +1,191 @@
+//! Serialization and deserialization of NaN-tagged values, via [`serde`].
+//!
+//! A raw NaN-tagged `u64` is opaque, so in human-readable formats values are (de)serialized as an externally tagged enum:
+//! `{ "float": 1.5 }`, `{ "uint": 42 }` or `{ "cell": { "tag": 4, "data": 12345 } }`.
+//!
+//! Floats that aren't finite are written as the string sentinels `"NaN"`, `"inf"` and `"-inf"`,
+//! as formats like JSON can't represent them natively.
+//!
+//! Compact formats, such as bincode, get the raw bits instead; as 8 little-endian bytes.
+
+use core::fmt;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{self, Unexpected, Visitor};
+use serde::ser;
+use super::{decode, uint, wrap_float, HashableValue, NanError, ValueKind};
+
+#[cfg(feature = "cell")]
+use super::cell;
+
+/// A NaN-tagged value that implements [`Serialize`] and [`Deserialize`].
+///
+/// In human-readable formats, serializing fails for values that [`decode`] as [`ValueKind::Unknown`],
+/// and deserializing fails for payloads that don't fit into their kind;
+/// compact formats carry any bits as-is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct NanValue(pub u64);
+
+/// The serialized form of a [`NanValue`].
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "NanValue", rename_all = "lowercase")]
+enum Repr {
+    Float(Float),
+    Uint(u64),
+    #[cfg(feature = "cell")]
+    Cell {
+        tag: u8,
+        data: u64
+    },
+}
+
+/// A float that is written as a string sentinel if it isn't finite.
+struct Float(f64);
+
+impl Serialize for Float {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            x if x.is_nan() => serializer.serialize_str("NaN"),
+            f64::INFINITY => serializer.serialize_str("inf"),
+            f64::NEG_INFINITY => serializer.serialize_str("-inf"),
+            x => serializer.serialize_f64(x)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Float {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FloatVisitor;
+
+        impl Visitor<'_> for FloatVisitor {
+            type Value = Float;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str(r#"a number, "NaN", "inf" or "-inf""#)
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Float, E> {
+                Ok(Float(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Float, E> {
+                Ok(Float(v as f64))
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Float, E> {
+                Ok(Float(v as f64))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Float, E> {
+                match v {
+                    "NaN" => Ok(Float(f64::NAN)),
+                    "inf" => Ok(Float(f64::INFINITY)),
+                    "-inf" => Ok(Float(f64::NEG_INFINITY)),
+                    _ => Err(E::invalid_value(Unexpected::Str(v), &self))
+                }
+            }
+        }
+
+        deserializer.deserialize_any(FloatVisitor)
+    }
+}
+
+impl Serialize for NanValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return self.0.to_le_bytes().serialize(serializer)
+        }
+        let repr = match decode(self.0) {
+            ValueKind::Float(x) => Repr::Float(Float(x)),
+            ValueKind::Uint(data) => Repr::Uint(data),
+            #[cfg(feature = "cell")]
+            ValueKind::Cell { tag, data } => Repr::Cell { tag: tag.to_u8(), data },
+            ValueKind::Unknown(_) => return Err(ser::Error::custom("can't serialize an unknown NaN-tagged value"))
+        };
+        repr.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NanValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return <[u8; 8]>::deserialize(deserializer).map(|bytes| NanValue(u64::from_le_bytes(bytes)))
+        }
+        let bits = match Repr::deserialize(deserializer)? {
+            Repr::Float(Float(x)) => wrap_float(x),
+            Repr::Uint(data) => match uint::wrap(data) {
+                // The uint `0` may be identical to the canonical `NaN`, and thus not decode as a uint.
+                Some(bits) if matches!(decode(bits), ValueKind::Uint(_)) => bits,
+                Some(_) => return Err(de::Error::invalid_value(Unexpected::Unsigned(data), &"a uint distinct from NaN")),
+                None => return Err(de::Error::custom(NanError::OutOfRange))
+            },
+            #[cfg(feature = "cell")]
+            Repr::Cell { tag, data } => {
+                let tag = cell::CellTag::from_u8(tag)
+                    .ok_or_else(|| de::Error::invalid_value(Unexpected::Unsigned(tag as u64), &"a cell tag in 1..=7"))?;
+                cell::from_tag_and_data(tag, data).ok_or_else(|| de::Error::custom(NanError::OutOfRange))?
+            },
+        };
+        Ok(NanValue(bits))
+    }
+}
+
+impl Serialize for HashableValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        NanValue(self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for HashableValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        NanValue::deserialize(deserializer).map(|value| HashableValue(value.0))
+    }
+}
+
+#[cfg(test)]
+fn roundtrip(bits: u64, json: &str) {
+    assert_eq!(serde_json::to_string(&NanValue(bits)).unwrap(), json);
+    assert_eq!(serde_json::from_str::<NanValue>(json).unwrap(), NanValue(bits));
+}
+
+#[test]
+fn test_serde_float() {
+    roundtrip(1.5f64.to_bits(), r#"{"float":1.5}"#);
+    roundtrip(wrap_float(f64::NAN), r#"{"float":"NaN"}"#);
+    roundtrip(f64::INFINITY.to_bits(), r#"{"float":"inf"}"#);
+    roundtrip(f64::NEG_INFINITY.to_bits(), r#"{"float":"-inf"}"#);
+    assert_eq!(serde_json::from_str::<NanValue>(r#"{"float":2}"#).unwrap(), NanValue(2.0f64.to_bits()));
+    assert!(serde_json::from_str::<NanValue>(r#"{"float":"nope"}"#).is_err());
+}
+
+#[test]
+fn test_serde_uint() {
+    roundtrip(uint::wrap_unchecked(42), r#"{"uint":42}"#);
+    assert!(serde_json::from_str::<NanValue>(r#"{"uint":2251799813685248}"#).is_err());
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_serde_cell() {
+    let cell = cell::from_tag_and_data(cell::CellTag::Tag4, 12345).unwrap();
+    roundtrip(cell, r#"{"cell":{"tag":4,"data":12345}}"#);
+    assert!(serde_json::from_str::<NanValue>(r#"{"cell":{"tag":0,"data":1}}"#).is_err());
+    assert!(serde_json::from_str::<NanValue>(r#"{"cell":{"tag":8,"data":1}}"#).is_err());
+    assert!(serde_json::from_str::<NanValue>(r#"{"cell":{"tag":4,"data":281474976710656}}"#).is_err());
+    assert!(serde_json::to_string(&NanValue(cell::CELL_MARKER_BITS | 1)).is_err());
+}
+
+#[test]
+fn test_serde_compact() {
+    let values = [1.5f64.to_bits(), wrap_float(f64::NAN), uint::wrap_unchecked(42), crate::cons::SIGN_BIT | crate::cons::NAN_BITS | 1];
+    for bits in values {
+        let bytes = bincode::serialize(&NanValue(bits)).unwrap();
+        assert_eq!(bytes, bits.to_le_bytes());
+        assert_eq!(bincode::deserialize::<NanValue>(&bytes).unwrap(), NanValue(bits));
+    }
+    
+    // The same value round-trips through both forms.
+    let value = NanValue(uint::wrap_unchecked(42));
+    roundtrip(value.0, r#"{"uint":42}"#);
+    assert_eq!(bincode::deserialize::<NanValue>(&bincode::serialize(&value).unwrap()).unwrap(), value);
+}
@@ -0,0 +1,99 @@
+//! Handling of values marked as a 'sint' (`SIGN_BIT | NAN_BITS | SINT_TAG_BITS`): 48-bit signed integers.
+//! 
+//! Bit Layout is as follows:
+//! ```text
+//! 1111 1111 1111 1011 xxxx xxxx xxxx xxxx xxxx xxxx xxxx xxxx xxxx xxxx xxxx xxxx
+//! ```
+//! 
+//! - The marker is that of a [`crate::cell`] with the reserved tag `0b011`.
+//! - Data `x`, 48 bits; the integer in two's-complement.
+//! 
+//! **Note:** NaN-space has no room for a full 52-bit signed integer next to the uints and cells,
+//! which is why sints borrow a reserved cell tag and are limited to [`MIN_SINT`]`..=`[`MAX_SINT`].
+
+use super::{cons::*, IntoRawBits64};
+
+/// Indicates that the value is a sint.
+pub const SINT_MARKER_BITS: u64 = SIGN_BIT | NAN_BITS | SINT_TAG_BITS;
+
+/// Masks the bits ([`SINT_MARKER_BITS`]) that indicate that the value is a sint; the cell marker and tag.
+pub const SINT_MARKER_MASK: u64 = SIGN_BIT | NAN_BITS | 0x0007000000000000;
+
+/// Masks the bits that actually hold the data of the sint.
+pub const SINT_DATA_BITS: u64 = !SINT_MARKER_MASK;
+
+/// The smallest integer that can be stored in a sint; the former name of [`MIN_SINT`].
+#[deprecated(note = "use `cons::MIN_SINT` instead")]
+pub const SINT_MIN: i64 = MIN_SINT;
+
+/// The largest integer that can be stored in a sint; the former name of [`MAX_SINT`].
+#[deprecated(note = "use `cons::MAX_SINT` instead")]
+pub const SINT_MAX: i64 = MAX_SINT;
+
+// Compile-time checks that a sint is exactly a cell with the reserved tag.
+#[cfg(feature = "cell")]
+const _: () = {
+    use crate::cell::*;
+    assert!(SINT_MARKER_MASK == CELL_MARKER_BITS | CELL_TAG_BITS);
+    assert!(SINT_DATA_BITS == CELL_DATA_BITS);
+};
+
+/// Returns wether the given value is a sint.
+#[inline(always)]
+pub fn is_sint(value: impl IntoRawBits64) -> bool {
+    (value.as_raw_bits_64() & SINT_MARKER_MASK) == SINT_MARKER_BITS
+}
+
+/// Wraps the given [`i64`] as a sint, if it lies within [`MIN_SINT`]`..=`[`MAX_SINT`].
+#[inline(always)]
+pub fn wrap_sint(value: i64) -> Option<u64> {
+    match (MIN_SINT..=MAX_SINT).contains(&value) {
+        true => Some(SINT_MARKER_BITS | (value as u64 & SINT_DATA_BITS)),
+        false => None
+    }
+}
+
+/// Unwraps the data of the given value as sign-extended [`i64`], without checking if it is a sint.
+#[inline(always)]
+pub fn unwrap_sint_unchecked(value: impl IntoRawBits64) -> i64 {
+    // Shift the 48 data bits to the top, so that the arithmetic shift back down sign-extends them.
+    ((value.as_raw_bits_64() << 16) as i64) >> 16
+}
+
+/// Unwraps the data of the given value as sign-extended [`i64`], if it is a sint.
+#[inline(always)]
+pub fn unwrap_sint(value: impl IntoRawBits64) -> Option<i64> {
+    match is_sint(value) {
+        true => Some(unwrap_sint_unchecked(value)),
+        false => None
+    }
+}
+
+#[test]
+fn test_sint_roundtrip() {
+    for value in [MIN_SINT, MIN_SINT + 1, -4711, -1, 0, 1, 4711, MAX_SINT - 1, MAX_SINT] {
+        let sint = wrap_sint(value).unwrap();
+        assert!(is_sint(sint));
+        assert!(crate::is_nanval(sint));
+        assert_eq!(unwrap_sint(sint), Some(value));
+    }
+    assert_eq!(wrap_sint(-1), Some(SINT_MARKER_BITS | SINT_DATA_BITS));
+}
+
+#[test]
+fn test_sint_out_of_range() {
+    assert_eq!(wrap_sint(MIN_SINT - 1), None);
+    assert_eq!(wrap_sint(MAX_SINT + 1), None);
+    assert_eq!(wrap_sint(-(1 << 51)), None);
+    assert_eq!(wrap_sint((1 << 51) - 1), None);
+    assert_eq!(wrap_sint(i64::MIN), None);
+    assert_eq!(wrap_sint(i64::MAX), None);
+}
+
+#[test]
+fn test_sint_distinct() {
+    assert!(!is_sint(1.5f64));
+    assert!(!is_sint(NAN_BITS | 42));
+    assert!(!is_sint(SIGN_BIT | NAN_BITS | SYMBOL_TAG_BITS | 42));
+    assert_eq!(unwrap_sint(SIGN_BIT | NAN_BITS | 42), None);
+}
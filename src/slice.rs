@@ -0,0 +1,104 @@
+//! A borrowed slice of NaN-tagged values, [`NanSlice`], with bulk classification.
+
+use super::{decode, ValueKind};
+
+#[cfg(feature = "cell")]
+use super::cell::CellTag;
+
+/// A borrowed slice of NaN-tagged values, classified via [`decode`].
+/// 
+/// Values that [`decode`] as [`ValueKind::Unknown`] are neither floats, uints nor cells.
+#[derive(Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct NanSlice<'a>(&'a [u64]);
+
+impl<'a> NanSlice<'a> {
+    /// Returns the number of floats in the slice.
+    pub fn count_floats(&self) -> usize {
+        self.iter_floats().count()
+    }
+    
+    /// Returns the number of uints in the slice.
+    pub fn count_uints(&self) -> usize {
+        self.iter_uints().count()
+    }
+    
+    /// Returns the number of cells in the slice.
+    #[cfg(feature = "cell")]
+    pub fn count_cells(&self) -> usize {
+        self.iter_cells().count()
+    }
+    
+    /// Returns an iterator over the floats in the slice.
+    pub fn iter_floats(&self) -> impl Iterator<Item = f64> + 'a {
+        self.0.iter().filter_map(|&bits| match decode(bits) {
+            ValueKind::Float(value) => Some(value),
+            _ => None
+        })
+    }
+    
+    /// Returns an iterator over the data of the uints in the slice.
+    pub fn iter_uints(&self) -> impl Iterator<Item = u64> + 'a {
+        self.0.iter().filter_map(|&bits| match decode(bits) {
+            ValueKind::Uint(data) => Some(data),
+            _ => None
+        })
+    }
+    
+    /// Returns an iterator over the tag and data of the cells in the slice.
+    #[cfg(feature = "cell")]
+    pub fn iter_cells(&self) -> impl Iterator<Item = (CellTag, u64)> + 'a {
+        self.0.iter().filter_map(|&bits| match decode(bits) {
+            ValueKind::Cell { tag, data } => Some((tag, data)),
+            _ => None
+        })
+    }
+}
+
+impl<'a> From<&'a [u64]> for NanSlice<'a> {
+    fn from(slice: &'a [u64]) -> Self {
+        Self(slice)
+    }
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<'a> From<&'a alloc::vec::Vec<u64>> for NanSlice<'a> {
+    fn from(vec: &'a alloc::vec::Vec<u64>) -> Self {
+        Self(vec)
+    }
+}
+
+#[test]
+fn test_nan_slice() {
+    use super::{cons::*, uint};
+    let values = [
+        1.5f64.to_bits(),
+        CANONICAL_NAN_BITS,
+        uint::wrap_unchecked(42),
+        uint::wrap_unchecked(7),
+        SIGN_BIT | NAN_BITS,
+    ];
+    let slice = NanSlice::from(&values[..]);
+    assert_eq!(slice.count_floats(), 2);
+    assert_eq!(slice.iter_floats().next(), Some(1.5));
+    assert_eq!(slice.count_uints(), 2);
+    assert!(slice.iter_uints().eq([42, 7]));
+    #[cfg(feature = "std")]
+    assert_eq!(NanSlice::from(&values.to_vec()).count_uints(), 2);
+}
+
+#[test]
+#[cfg(feature = "cell")]
+fn test_nan_slice_cells() {
+    use super::cell;
+    let values = [
+        2.5f64.to_bits(),
+        cell::NULL,
+        cell::from_tag_and_data(CellTag::Tag5, 12345).unwrap(),
+        cell::CELL_MARKER_BITS | 42,
+    ];
+    let slice = NanSlice::from(&values[..]);
+    assert_eq!(slice.count_cells(), 2);
+    assert!(slice.iter_cells().eq([(CellTag::Tag1, 1), (CellTag::Tag5, 12345)]));
+    assert_eq!(slice.count_floats() + slice.count_uints(), 1);
+}
@@ -0,0 +1,55 @@
+//! Serialization and deserialization of [`NanVal`]s, via [`speedy`].
+//! 
+//! A [`NanVal`] is written as its raw bits, a little-endian `u64`, regardless of the endianness of the context;
+//! so the output is the same on every platform.
+//! 
+//! **Note:** The bits of a pointer-cell are written as-is; the address is only meaningful within the process that created it,
+//! so such values must not be read back anywhere else.
+
+use speedy::{Context, Readable, Reader, Writable, Writer};
+use super::NanVal;
+
+impl<'a, C: Context> Readable<'a, C> for NanVal {
+    #[inline]
+    fn read_from<R: Reader<'a, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        let mut bytes = [0; 8];
+        reader.read_bytes(&mut bytes)?;
+        Ok(NanVal(u64::from_le_bytes(bytes)))
+    }
+    
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        8
+    }
+}
+
+impl<C: Context> Writable<C> for NanVal {
+    #[inline]
+    fn write_to<T: ?Sized + Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        writer.write_bytes(&self.0.to_le_bytes())
+    }
+    
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        Ok(8)
+    }
+}
+
+#[test]
+fn test_speedy_roundtrip() {
+    use speedy::Endianness;
+    let values = vec![
+        NanVal::from(1.5),
+        NanVal::from(f64::NAN),
+        NanVal::from(crate::uint::wrap_unchecked(0)),
+        NanVal::from(crate::uint::wrap_unchecked(42)),
+        NanVal::from(crate::uint::wrap_unchecked(crate::uint::MAX_VALUE)),
+    ];
+    let bytes = values.write_to_vec().unwrap();
+    assert_eq!(Vec::<NanVal>::read_from_buffer(&bytes).unwrap(), values);
+    
+    // The endianness of the context doesn't matter.
+    let value = NanVal::from(1.5);
+    assert_eq!(value.write_to_vec_with_ctx(Endianness::BigEndian).unwrap(), 1.5f64.to_bits().to_le_bytes());
+    assert_eq!(NanVal::read_from_buffer_with_ctx(Endianness::BigEndian, &1.5f64.to_bits().to_le_bytes()).unwrap(), value);
+}
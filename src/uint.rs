@@ -1,6 +1,6 @@
 //! Handling of values marked as a 'uint' (`!SIGN_BIT | NAN_BITS`): 52-bit integers.
 
-use super::{cons::*, IntoRawBits64};
+use super::{cons::*, IntoRawBits64, NanError};
 use core::num::NonZeroU64;
 
 /// Indicates that the value is a uint; the sign-bit is **not** set.
@@ -12,10 +12,40 @@ pub const UINT_MARKER_MASK: u64 = SIGN_BIT | NAN_BITS;
 /// Masks the bits that actually hold the data of the uint.
 pub const UINT_DATA_BITS: u64 = !UINT_MARKER_MASK;
 
+/// Masks the bits that a value must fit into to be stored as a uint; same as [`UINT_DATA_BITS`].
+pub const DATA_MASK: u64 = UINT_DATA_BITS;
+
+/// The smallest value that can be stored in a uint.
+/// 
+/// **Note:** By default the uint `0` has the same bits as [`CANONICAL_NAN_BITS`], and thus is the float `NaN` to [`crate::is_float`].
+pub const MIN_VALUE: u64 = 0;
+
+/// The largest value that can be stored in a uint: `2 ** 51 - 1`.
+/// 
+/// ```
+/// use nanval::uint;
+/// assert!(uint::wrap(uint::MAX_VALUE).is_some());
+/// assert!(uint::wrap(uint::MAX_VALUE + 1).is_none());
+/// ```
+pub const MAX_VALUE: u64 = UINT_DATA_BITS;
+
+// Compile-time checks that the marker and data bits partition the value.
+const _: () = {
+    assert!(UINT_MARKER_BITS & SIGN_BIT == 0);
+    assert!(UINT_MARKER_MASK & UINT_DATA_BITS == 0);
+    assert!(UINT_MARKER_MASK | UINT_DATA_BITS == u64::MAX);
+};
+
 /// Returns wether the given value is a uint.
 #[inline(always)]
 pub fn is_uint(value: impl IntoRawBits64) -> bool {
-    (value.as_raw_bits_64() & (UINT_MARKER_MASK)) == UINT_MARKER_BITS
+    is_uint_u64(value.as_raw_bits_64())
+}
+
+/// Returns wether the given bits are a uint; the `const` version of [`is_uint`].
+#[inline(always)]
+pub const fn is_uint_u64(value: u64) -> bool {
+    (value & (UINT_MARKER_MASK)) == UINT_MARKER_BITS
 }
 
 /// Returns wether the given value is *not* a uint.
@@ -46,4 +76,291 @@ pub fn unwrap_uint_nonzero(value: impl IntoRawBits64) -> Option<NonZeroU64> {
         true => NonZeroU64::new(unwrap_uint_unchecked(value)),
         false => None
     }
-}
\ No newline at end of file
+}
+
+/// Unwraps the data of the given value as [`u64`] if it is a uint, or returns the given `default` otherwise.
+#[inline(always)]
+pub fn unwrap_uint_or(value: impl IntoRawBits64, default: u64) -> u64 {
+    unwrap_uint(value).unwrap_or(default)
+}
+
+/// Wraps the given [`u64`] as a uint, if it fits into the [`UINT_DATA_BITS`].
+#[inline(always)]
+pub const fn wrap(value: u64) -> Option<u64> {
+    match value & UINT_DATA_BITS == value {
+        true => Some(UINT_MARKER_BITS | value),
+        false => None
+    }
+}
+
+/// Wraps the given [`u64`] as a uint, without checking if it fits into the [`UINT_DATA_BITS`].
+/// 
+/// Any bits outside of the [`UINT_DATA_BITS`] are discarded; in debug builds, this function panics instead.
+#[inline(always)]
+pub const fn wrap_unchecked(value: u64) -> u64 {
+    debug_assert!(value & UINT_DATA_BITS == value, "value does not fit into a uint");
+    UINT_MARKER_BITS | (value & UINT_DATA_BITS)
+}
+
+/// Boxes the given [`u64`] as a uint, if it fits into the [`UINT_DATA_BITS`].
+/// 
+/// **Note:** Not every integer up to [`MAX_INT`] fits, as there are fewer data bits than an `f64` has mantissa bits.
+#[inline(always)]
+pub fn box_u64(value: u64) -> Result<u64, NanError> {
+    wrap(value).ok_or(NanError::OutOfRange)
+}
+
+/// An iterator over a range of uints, yielding each step as a uint-value.
+/// 
+/// The range is half-open; the `end` is *not* included.
+#[derive(Clone)]
+pub struct BoxedUintRange {
+    next: u64,
+    end: u64,
+}
+
+impl BoxedUintRange {
+    /// Creates a new range between the given values, if both of them are uints.
+    pub fn new(start: impl IntoRawBits64, end: impl IntoRawBits64) -> Option<Self> {
+        Some(Self {
+            next: unwrap_uint(start)?,
+            end: unwrap_uint(end)?
+        })
+    }
+}
+
+impl Iterator for BoxedUintRange {
+    type Item = u64;
+    
+    #[inline]
+    fn next(&mut self) -> Option<u64> {
+        if self.next >= self.end {
+            return None
+        }
+        let value = wrap_unchecked(self.next);
+        self.next += 1;
+        Some(value)
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end.saturating_sub(self.next);
+        match usize::try_from(len) {
+            Ok(len) => (len, Some(len)),
+            Err(_) => (usize::MAX, None)
+        }
+    }
+}
+
+#[test]
+fn test_boxed_uint_range() {
+    let start = UINT_MARKER_BITS | 3;
+    let end = UINT_MARKER_BITS | 7;
+    
+    let mut values = [0u64; 4];
+    let range = BoxedUintRange::new(start, end).unwrap();
+    assert_eq!(range.size_hint(), (4, Some(4)));
+    for (slot, value) in values.iter_mut().zip(range) {
+        assert!(is_uint(value));
+        *slot = unwrap_uint_unchecked(value);
+    }
+    assert_eq!(values, [3, 4, 5, 6]);
+    
+    assert_eq!(BoxedUintRange::new(end, start).unwrap().count(), 0);
+    assert!(BoxedUintRange::new(1.5f64, end).is_none());
+    assert!(BoxedUintRange::new(start, 1.5f64).is_none());
+}
+
+#[test]
+fn test_unwrap_uint_or() {
+    assert_eq!(unwrap_uint_or(UINT_MARKER_BITS | 42, 7), 42);
+    assert_eq!(unwrap_uint_or(1.5f64, 7), 7);
+    assert_eq!(unwrap_uint_or(SIGN_BIT | NAN_BITS | 42, 7), 7);
+}
+
+#[test]
+fn test_box_u64() {
+    assert_eq!(box_u64(0).map(unwrap_uint), Ok(Some(0)));
+    assert_eq!(box_u64(42).map(unwrap_uint), Ok(Some(42)));
+    assert_eq!(box_u64(UINT_DATA_BITS).map(unwrap_uint), Ok(Some(UINT_DATA_BITS)));
+    assert_eq!(box_u64(UINT_DATA_BITS + 1), Err(NanError::OutOfRange));
+    assert_eq!(box_u64(MAX_INT), Err(NanError::OutOfRange));
+    assert_eq!(box_u64(MAX_INT + 1), Err(NanError::OutOfRange));
+}
+
+#[test]
+fn test_wrap() {
+    for value in [0, 1, 42, UINT_DATA_BITS - 1, UINT_DATA_BITS] {
+        assert_eq!(unwrap_uint(wrap(value).unwrap()), Some(value));
+        assert_eq!(wrap_unchecked(value), wrap(value).unwrap());
+    }
+    assert_eq!(wrap(UINT_DATA_BITS + 1), None);
+    assert_eq!(wrap(u64::MAX), None);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "does not fit")]
+fn test_wrap_unchecked_overflow() {
+    wrap_unchecked(u64::MAX);
+}
+
+#[test]
+fn test_const_wrap() {
+    const ANSWER: u64 = wrap_unchecked(42);
+    const CHECKED: Option<u64> = wrap(42);
+    assert_eq!(unwrap_uint(ANSWER), Some(42));
+    assert_eq!(CHECKED, Some(ANSWER));
+}
+
+/// What arithmetic on uints does when the result doesn't fit into the [`UINT_DATA_BITS`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverflowMode {
+    /// Wraps around at the [`UINT_DATA_BITS`].
+    /// 
+    /// **Note:** A result of `0` is the uint `0`, which is indistinguishable from [`NAN_BITS`].
+    Wrap,
+    /// Clamps the result to [`MAX_VALUE`].
+    Saturate,
+    /// Returns [`NanError::Overflow`].
+    Error,
+}
+
+/// Adds the given uints, handling overflow as selected by the given [`OverflowMode`].
+/// 
+/// Returns [`NanError::WrongKind`] if either value is not a uint.
+#[inline(always)]
+pub fn add_with(a: impl IntoRawBits64, b: impl IntoRawBits64, mode: OverflowMode) -> Result<u64, NanError> {
+    let a = unwrap_uint(a).ok_or(NanError::WrongKind)?;
+    let b = unwrap_uint(b).ok_or(NanError::WrongKind)?;
+    // Both operands are at most 51 bits, so their sum can't overflow a `u64`.
+    let sum = a + b;
+    match (sum > MAX_VALUE, mode) {
+        (false, _) => Ok(wrap_unchecked(sum)),
+        (true, OverflowMode::Wrap) => Ok(wrap_unchecked(sum & UINT_DATA_BITS)),
+        (true, OverflowMode::Saturate) => Ok(wrap_unchecked(MAX_VALUE)),
+        (true, OverflowMode::Error) => Err(NanError::Overflow),
+    }
+}
+
+#[test]
+fn test_add_with() {
+    let max = wrap_unchecked(MAX_VALUE);
+    for mode in [OverflowMode::Wrap, OverflowMode::Saturate, OverflowMode::Error] {
+        assert_eq!(add_with(wrap_unchecked(40), wrap_unchecked(2), mode), Ok(wrap_unchecked(42)));
+        assert_eq!(add_with(max, wrap_unchecked(0), mode), Ok(max));
+        assert_eq!(add_with(1.5f64, wrap_unchecked(2), mode), Err(NanError::WrongKind));
+    }
+    assert_eq!(add_with(max, wrap_unchecked(2), OverflowMode::Wrap), Ok(wrap_unchecked(1)));
+    assert_eq!(add_with(max, wrap_unchecked(2), OverflowMode::Saturate), Ok(max));
+    assert_eq!(add_with(max, wrap_unchecked(2), OverflowMode::Error), Err(NanError::Overflow));
+}
+
+/// Applies the fallible transform `f` to the data of the given uint, and boxes the result as a uint.
+/// 
+/// Returns [`NanError::WrongKind`] if the value isn't a uint,
+/// [`NanError::Overflow`] if `f` returns `None`,
+/// and [`NanError::OutOfRange`] if the result doesn't fit into the [`UINT_DATA_BITS`].
+#[inline(always)]
+pub fn try_map(value: impl IntoRawBits64, f: impl FnOnce(u64) -> Option<u64>) -> Result<u64, NanError> {
+    let data = unwrap_uint(value).ok_or(NanError::WrongKind)?;
+    box_u64(f(data).ok_or(NanError::Overflow)?)
+}
+
+#[test]
+fn test_try_map() {
+    assert_eq!(try_map(wrap_unchecked(21), |x| x.checked_mul(2)), Ok(wrap_unchecked(42)));
+    assert_eq!(try_map(wrap_unchecked(MAX_VALUE), |x| x.checked_mul(2)), Err(NanError::OutOfRange));
+    assert_eq!(try_map(wrap_unchecked(1), |x| x.checked_sub(2)), Err(NanError::Overflow));
+    assert_eq!(try_map(1.5f64, Some), Err(NanError::WrongKind));
+}
+
+/// Adds the given uints, returning `None` on overflow or if either value is not a uint.
+#[inline(always)]
+pub fn checked_add(a: impl IntoRawBits64, b: impl IntoRawBits64) -> Option<u64> {
+    add_with(a, b, OverflowMode::Error).ok()
+}
+
+/// Subtracts the uint `b` from the uint `a`, returning `None` on underflow or if either value is not a uint.
+#[inline(always)]
+pub fn checked_sub(a: impl IntoRawBits64, b: impl IntoRawBits64) -> Option<u64> {
+    let diff = unwrap_uint(a)?.checked_sub(unwrap_uint(b)?)?;
+    Some(wrap_unchecked(diff))
+}
+
+/// Adds the given uints, wrapping around at the [`UINT_DATA_BITS`]; returns `None` if either value is not a uint.
+#[inline(always)]
+pub fn wrapping_add(a: impl IntoRawBits64, b: impl IntoRawBits64) -> Option<u64> {
+    add_with(a, b, OverflowMode::Wrap).ok()
+}
+
+/// Subtracts the uint `b` from the uint `a`, wrapping around at the [`UINT_DATA_BITS`]; returns `None` if either value is not a uint.
+#[inline(always)]
+pub fn wrapping_sub(a: impl IntoRawBits64, b: impl IntoRawBits64) -> Option<u64> {
+    let diff = unwrap_uint(a)?.wrapping_sub(unwrap_uint(b)?);
+    // Masking keeps any borrow out of the marker bits.
+    Some(wrap_unchecked(diff & UINT_DATA_BITS))
+}
+
+#[test]
+fn test_checked_and_wrapping() {
+    let max = wrap_unchecked(MAX_VALUE);
+    let one = wrap_unchecked(1);
+    let two = wrap_unchecked(2);
+    
+    assert_eq!(checked_add(one, two), Some(wrap_unchecked(3)));
+    assert_eq!(checked_add(max, one), None);
+    assert_eq!(checked_sub(two, one), Some(one));
+    assert_eq!(checked_sub(one, two), None);
+    
+    assert_eq!(wrapping_add(max, two), Some(one));
+    assert_eq!(wrapping_sub(one, two), Some(max));
+    assert!(is_uint(wrapping_sub(one, two).unwrap()));
+    
+    let ops: [fn(f64, u64) -> Option<u64>; 4] = [checked_add, checked_sub, wrapping_add, wrapping_sub];
+    for op in ops {
+        assert_eq!(op(1.5, one), None);
+    }
+}
+
+/// Wraps the given index as a uint, if it fits.
+/// 
+/// **Note:** On 64-bit platforms, where `usize` is as large as `u64`, values above [`MAX_VALUE`] return `None`.
+#[inline(always)]
+pub fn from_usize(n: usize) -> Option<u64> {
+    wrap(n as u64)
+}
+
+/// Unwraps the given uint as an index, if it is a uint that fits into a `usize`.
+#[inline(always)]
+pub fn to_usize(value: impl IntoRawBits64) -> Option<usize> {
+    usize::try_from(unwrap_uint(value)?).ok()
+}
+
+/// Wraps the given boolean as the uint `0` or `1`; this always succeeds.
+/// 
+/// **Note:** By default, `false` is indistinguishable from the float `NaN`; see [`MIN_VALUE`].
+#[inline(always)]
+pub const fn from_bool(b: bool) -> u64 {
+    wrap_unchecked(b as u64)
+}
+
+/// Wraps the given [`u32`] as a uint; this always succeeds, as [`u32::MAX`] is smaller than [`MAX_VALUE`].
+#[inline(always)]
+pub const fn from_u32(n: u32) -> u64 {
+    debug_assert!((n as u64) <= MAX_VALUE);
+    wrap_unchecked(n as u64)
+}
+
+#[test]
+fn test_conversions() {
+    assert_eq!(from_usize(42), Some(wrap_unchecked(42)));
+    assert_eq!(to_usize(wrap_unchecked(42)), Some(42));
+    assert_eq!(to_usize(1.5f64), None);
+    #[cfg(target_pointer_width = "64")]
+    assert_eq!(from_usize(usize::MAX), None);
+    
+    assert_eq!(unwrap_uint(from_bool(true)), Some(1));
+    assert_eq!(unwrap_uint(from_bool(false)), Some(0));
+    assert_eq!(unwrap_uint(from_u32(u32::MAX)), Some(u32::MAX as u64));
+    const { assert!(is_uint_u64(from_u32(7))) };
+}
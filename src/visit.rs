@@ -0,0 +1,80 @@
+//! Push-style decoding of NaN-tagged values, for hot loops that shouldn't construct an intermediate value.
+
+use super::{cell::{self, CellTag}, uint, is_float, unwrap_float_unchecked};
+
+/// Receives the decoded contents of values passed to [`visit`].
+pub trait NanVisitor {
+    /// Called for a valid `f64`.
+    fn float(&mut self, value: f64);
+    
+    /// Called for a [`uint`], with its data.
+    fn uint(&mut self, value: u64);
+    
+    /// Called for a [`cell`] with a defined tag, with its data.
+    fn cell(&mut self, tag: CellTag, data: u64);
+    
+    /// Called for a cell with the undefined tag `0b000`, with its raw bits; does nothing by default.
+    fn unknown(&mut self, _bits: u64) {}
+}
+
+/// Decodes the given value, calling the matching method of the given visitor.
+#[inline]
+pub fn visit(word: u64, visitor: &mut impl NanVisitor) {
+    if is_float(word) {
+        visitor.float(unwrap_float_unchecked(word))
+    } else if uint::is_uint(word) {
+        visitor.uint(uint::unwrap_uint_unchecked(word))
+    } else {
+        match cell::unwrap_tag(word) {
+            Some(tag) => visitor.cell(tag, cell::unwrap_cell_unchecked(word)),
+            None => visitor.unknown(word)
+        }
+    }
+}
+
+#[test]
+fn test_counting_visitor() {
+    #[derive(Default)]
+    struct Counter {
+        floats: usize,
+        uints: u64,
+        cells: [usize; 8],
+        unknown: usize,
+    }
+    
+    impl NanVisitor for Counter {
+        fn float(&mut self, _: f64) {
+            self.floats += 1;
+        }
+        fn uint(&mut self, value: u64) {
+            self.uints += value;
+        }
+        fn cell(&mut self, tag: CellTag, _: u64) {
+            self.cells[(tag as u64 >> 48) as usize] += 1;
+        }
+        fn unknown(&mut self, _: u64) {
+            self.unknown += 1;
+        }
+    }
+    
+    let words = [
+        1.5f64.to_bits(),
+        crate::wrap_float(f64::NAN),
+        f64::NEG_INFINITY.to_bits(),
+        uint::wrap_unchecked(40),
+        uint::wrap_unchecked(2),
+        cell::symbol(7).unwrap(),
+        cell::from_tag_and_data(CellTag::Tag5, 1).unwrap(),
+        cell::from_tag_and_data(CellTag::Tag5, 2).unwrap(),
+        cell::CELL_MARKER_BITS | 42,
+    ];
+    
+    let mut counter = Counter::default();
+    for word in words {
+        visit(word, &mut counter);
+    }
+    assert_eq!(counter.floats, 3);
+    assert_eq!(counter.uints, 42);
+    assert_eq!(counter.cells, [0, 0, 1, 0, 0, 2, 0, 0]);
+    assert_eq!(counter.unknown, 1);
+}
@@ -0,0 +1,47 @@
+//! Checks that a [`CellBox`] frees its buffer exactly once.
+//! 
+//! This lives in its own test-binary, as it installs a counting global allocator.
+#![cfg(all(feature = "std", feature = "cell"))]
+
+use nanval::cell::{CellBox, CellTag};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Address of the allocation being tracked.
+static TRACKED: AtomicUsize = AtomicUsize::new(0);
+
+/// Number of times the tracked allocation was freed.
+static FREED: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        System.alloc(layout)
+    }
+    
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if ptr as usize == TRACKED.load(Ordering::SeqCst) {
+            FREED.fetch_add(1, Ordering::SeqCst);
+        }
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[test]
+fn test_cell_box_frees_once() {
+    let buffer: Box<[u8]> = vec![0xAB; 64].into_boxed_slice();
+    TRACKED.store(buffer.as_ptr() as usize, Ordering::SeqCst);
+    
+    let cell_box = CellBox::new(CellTag::Tag5, buffer).unwrap();
+    assert_eq!(cell_box.len(), 64);
+    assert!(cell_box.iter().all(|b| *b == 0xAB));
+    assert_eq!(FREED.load(Ordering::SeqCst), 0, "freed while still boxed");
+    
+    drop(cell_box);
+    assert_eq!(FREED.load(Ordering::SeqCst), 1, "not freed exactly once");
+    TRACKED.store(0, Ordering::SeqCst);
+}
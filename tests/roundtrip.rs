@@ -0,0 +1,57 @@
+//! Roundtrip property tests for every kind of value, plus their edge cases.
+
+use nanval::{uint, unwrap_float, wrap_float};
+use proptest::prelude::*;
+
+/// Checks that the given float survives being wrapped and unwrapped; any `NaN` only has to stay a `NaN`.
+fn assert_float_roundtrip(x: f64) {
+    let unwrapped = unwrap_float(wrap_float(x)).expect("a wrapped float must unwrap");
+    match x.is_nan() {
+        true => assert!(unwrapped.is_nan()),
+        false => assert_eq!(unwrapped.to_bits(), x.to_bits())
+    }
+}
+
+proptest! {
+    #[test]
+    fn test_float_roundtrip(x in any::<f64>()) {
+        assert_float_roundtrip(x);
+    }
+
+    #[test]
+    fn test_uint_roundtrip(x in uint::MIN_VALUE..=uint::MAX_VALUE) {
+        prop_assert_eq!(uint::unwrap_uint(uint::wrap(x).unwrap()), Some(x));
+    }
+
+    #[test]
+    fn test_uint_out_of_range(x in (uint::MAX_VALUE + 1)..=u64::MAX) {
+        prop_assert_eq!(uint::wrap(x), None);
+    }
+}
+
+#[cfg(feature = "cell")]
+proptest! {
+    // Tag0 is excluded, as it is not a valid `CellTag`; a cell-marker with tag `0` decodes as unknown.
+    #[test]
+    fn test_cell_roundtrip(tag in 1u8..=7, data in 0..=nanval::cell::CELL_DATA_BITS) {
+        use nanval::cell::{self, CellTag};
+        let tag = CellTag::from_u8(tag).unwrap();
+        let value = cell::from_tag_and_data(tag, data).unwrap();
+        prop_assert_eq!(cell::unwrap_cell(value), Some(data));
+        prop_assert_eq!(cell::unwrap_tag(value), Some(tag));
+    }
+}
+
+#[test]
+fn test_float_edge_cases() {
+    for x in [f64::NAN, -f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 0.0, -0.0, f64::MIN, f64::MAX, f64::MIN_POSITIVE] {
+        assert_float_roundtrip(x);
+    }
+}
+
+#[test]
+fn test_uint_edge_cases() {
+    assert_eq!(uint::unwrap_uint(uint::wrap(uint::MIN_VALUE).unwrap()), Some(uint::MIN_VALUE));
+    assert_eq!(uint::unwrap_uint(uint::wrap(uint::MAX_VALUE).unwrap()), Some(uint::MAX_VALUE));
+    assert_eq!(uint::wrap(uint::MAX_VALUE + 1), None);
+}